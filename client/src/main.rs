@@ -1,4 +1,4 @@
-use argparse::{ArgumentParser, Store};
+use argparse::{ArgumentParser, Store, StoreOption};
 mod test_client;
 mod xml_client;
 mod xml_node;
@@ -19,6 +19,7 @@ fn main() {
     let mut reservation = "".to_string();
     let mut time: u128 = 1960;
     let mut test = false;
+    let mut seed: Option<u64> = None;
 
     {
         let mut parser = ArgumentParser::new();
@@ -39,6 +40,9 @@ fn main() {
             Store,
             "Run the test client instead of the xml client.",
         );
+        parser
+            .refer(&mut seed)
+            .add_option(&["-s", "--seed"], StoreOption, "Seed of the player");
         parser.parse_args_or_exit();
     }
 
@@ -49,6 +53,10 @@ fn main() {
 
     let mut player = Box::new(Algorithm::default());
     player.set_time_limit(time);
+    if let Some(seed) = seed {
+        println!("Seed: {}", seed);
+        player.set_seed(seed);
+    }
 
     if test {
         run_test_client(player);
@@ -6,7 +6,22 @@ pub fn run_test_client(mut player: Box<dyn Player>) {
         let mut fen = String::new();
         io::stdin().read_line(&mut fen).expect("Can't read line");
         fen.pop(); // remove \n
-        let state = GameState::from_fen(fen.clone());
+
+        // The test server seeds the player before each game
+        if let Some(seed) = fen.strip_prefix("seed: ") {
+            match seed.parse::<u64>() {
+                Ok(seed) => player.set_seed(seed),
+                Err(_) => println!("Invalid seed \"{}\"", seed),
+            }
+            continue;
+        }
+        let state = match GameState::try_from_fen(&fen) {
+            Ok(state) => state,
+            Err(error) => {
+                println!("Invalid fen \"{}\": {}", fen, error);
+                continue;
+            }
+        };
         if state.ply < 2 {
             player.on_reset();
         }
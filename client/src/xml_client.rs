@@ -1,4 +1,4 @@
-use game_sdk::{GameState, Player};
+use game_sdk::{GameRecord, GameState, Player, TerminationCause, Winner};
 extern crate xml;
 use super::xml_node::XmlNode;
 use std::io::{prelude::Write, BufReader, BufWriter};
@@ -14,6 +14,8 @@ pub struct XmlClient {
     room_id: Option<String>,
     player: Box<dyn Player>,
     time: Instant,
+    record: GameRecord,
+    last_action_time: Option<(u8, u64)>, // Ply and time used for the last action of the player
 }
 
 impl XmlClient {
@@ -26,12 +28,15 @@ impl XmlClient {
             room_id: None,
             player,
             time: Instant::now(),
+            // Player names are not known to the client
+            record: GameRecord::new(GameState::default().start_piece_type, Default::default()),
+            last_action_time: None,
         }
     }
 
     pub fn run(&mut self) {
         print!("Connecting to {}:{}... ", self.host, self.port);
-        let stream = TcpStream::connect(&format!("{}:{}", self.host, self.port))
+        let stream = TcpStream::connect(format!("{}:{}", self.host, self.port))
             .expect("Could not connect to server");
         println!("Connected");
         Self::write_to(&stream, "<protocol>");
@@ -55,7 +60,19 @@ impl XmlClient {
                     match data_class.as_str() {
                         "memento" => {
                             println!("Received memento:");
-                            node.as_memento(&mut self.state);
+                            let first_ply = self.state.ply;
+                            let actions = node.as_memento(&mut self.state);
+                            if self.state.ply == 0 {
+                                self.record.start_piece_type = self.state.start_piece_type;
+                            }
+                            for (i, action) in actions.into_iter().enumerate() {
+                                let ply = first_ply + i as u8;
+                                let time = match self.last_action_time {
+                                    Some((action_ply, time)) if action_ply == ply => time,
+                                    _ => 0,
+                                };
+                                self.record.push(action, time);
+                            }
                             println!("    fen: {}", self.state.to_fen());
                             println!("    ply: {}", self.state.ply);
                         }
@@ -69,9 +86,10 @@ impl XmlClient {
                             self.handle_result(node);
                             return;
                         }
-                        s => {
-                            println!("{} {}", s.to_string(), node.data.to_string());
-                        }
+                        s => match node.as_move() {
+                            Ok((action, _)) => println!("Received move: {}", action),
+                            Err(_) => println!("{} {}", s, node.data),
+                        },
                     }
                 }
                 "joined" => {
@@ -98,6 +116,9 @@ impl XmlClient {
         }
         self.time = Instant::now();
         let action = self.player.on_move_request(&self.state);
+        if let Err(reason) = self.state.check_action(&action) {
+            println!("Warning: The player chose an illegal action: {}", reason);
+        }
         let xml_move = action.to_xml(self.state.get_current_color());
         print!("Sending: {}", action);
         Self::write_to(
@@ -108,20 +129,25 @@ impl XmlClient {
                 xml_move
             ),
         );
-        println!(" Move sent after {}ms", self.time.elapsed().as_millis());
+        let elapsed = self.time.elapsed().as_millis();
+        println!(" Move sent after {}ms", elapsed);
+        self.last_action_time = Some((self.state.ply, elapsed as u64));
         self.time = Instant::now();
     }
 
-    pub fn handle_result(&self, node: XmlNode) {
+    pub fn handle_result(&mut self, node: XmlNode) {
         println!("Received result");
         let score = node.get_child("score").expect("Unable to read score");
+        let cause = score
+            .get_attribute("cause")
+            .expect("Error while reading cause");
+        self.record.finish(
+            &self.state,
+            TerminationCause::from_xml_name(cause).unwrap_or(TerminationCause::Unknown),
+        );
         println!(
             "{}",
-            match score
-                .get_attribute("cause")
-                .expect("Error while reading cause")
-                .as_str()
-            {
+            match cause.as_str() {
                 "REGULAR" => "The game ended regular.".to_string(),
                 "LEFT" => "The game ended because a player left.".to_string(),
                 "RULE_VIOLATION" => "The game ended because of a rule violation.".to_string(),
@@ -130,20 +156,9 @@ impl XmlClient {
                 _ => "The game ended because of a hard timeout.".to_string(),
             }
         );
-        let mut team_one_score: i16 = 0;
-        let mut team_two_score: i16 = 0;
+        let report = self.state.scores();
         println!("Color   | Fields | Monomino last | All placed | Score");
-        for color in 0..4 {
-            let fields = self.state.board[color].count_ones() as i16;
-            let all_placed = fields == 89;
-            let m_last = self.state.monomino_placed_last[color];
-            let mut score = fields;
-            if all_placed {
-                score += 15;
-                if m_last {
-                    score += 5;
-                }
-            }
+        for (color, score) in report.colors.iter().enumerate() {
             println!(
                 "{} | {:6} | {:13} | {:10} | {:5}",
                 match color {
@@ -152,25 +167,22 @@ impl XmlClient {
                     2 => "RED    ",
                     _ => "GREEN  ",
                 },
-                fields,
-                m_last,
-                all_placed,
-                score
+                score.cells,
+                self.state.monomino_placed_last[color],
+                score.all_placed(),
+                score.total()
             );
-            if color % 2 == 0 {
-                team_one_score += score;
-            } else {
-                team_two_score += score;
-            }
         }
-        println!("One score: {}", team_one_score);
-        println!("Two score: {}", team_two_score);
-        let result = team_one_score - team_two_score;
-        print!("Result: {} => ", self.state.game_result());
-        match result {
-            r if r > 0 => println!("Winner: Team One (BLUE, RED)"),
-            r if r < 0 => println!("Winner: Team Two (YELLOW, GREEN)"),
-            _ => println!("Draw"),
+        println!("One score: {}", report.teams[0]);
+        println!("Two score: {}", report.teams[1]);
+        print!("Result: {} => ", report.difference());
+        match report.winner {
+            Winner::Draw => println!("Draw"),
+            winner => println!("Winner: {}", winner),
+        }
+        match self.record.serialize() {
+            Ok(record) => println!("Game record:\n{}", record),
+            Err(error) => println!("Unable to serialize the game record: {}", error),
         }
     }
 
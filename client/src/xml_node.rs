@@ -1,7 +1,6 @@
-use game_sdk::{Action, Bitboard, GameState, PieceType};
+use game_sdk::{Action, Bitboard, Field, GameState, PieceType, XmlElement, XmlMoveError};
 use std::collections::{HashMap, VecDeque};
-use std::io::BufReader;
-use std::net::TcpStream;
+use std::io::Read;
 use xml::reader::{EventReader, XmlEvent};
 
 #[derive(Debug)]
@@ -22,7 +21,7 @@ impl XmlNode {
         }
     }
 
-    pub fn read_from(xml_parser: &mut EventReader<BufReader<&TcpStream>>) -> Self {
+    pub fn read_from<R: Read>(xml_parser: &mut EventReader<R>) -> Self {
         let mut node_stack: VecDeque<XmlNode> = VecDeque::new();
         let mut has_received_first = false;
         let mut final_node: Option<XmlNode> = None;
@@ -79,12 +78,18 @@ impl XmlNode {
         self.get_attribute("roomId").expect(err).to_string()
     }
 
-    pub fn as_memento(&self, state: &mut GameState) {
+    pub fn as_move(&self) -> Result<(Action, Option<usize>), XmlMoveError> {
+        // Returns the action and the color of a SetMove or SkipMove element
+        Action::from_xml(self)
+    }
+
+    pub fn as_memento(&self, state: &mut GameState) -> Vec<Action> {
         let err = "Error while parsing XML node to Memento";
-        self.get_child("state").expect(err).update_state(state);
+        self.get_child("state").expect(err).update_state(state)
     }
 
-    pub fn update_state(&self, state: &mut GameState) {
+    pub fn update_state(&self, state: &mut GameState) -> Vec<Action> {
+        // Returns the actions that lead to the new state
         // get the current ply
         let new_ply = self
             .get_attribute("turn")
@@ -112,13 +117,14 @@ impl XmlNode {
                 "PENTO_Y" => PieceType::YPentomino,
                 _ => panic!("Unknown start piece"),
             };
-            println!("    start piece: {}", state.start_piece_type.to_string());
-            return;
+            state.hash = state.compute_hash();
+            println!("    start piece: {}", state.start_piece_type);
+            return Vec::new();
         }
 
         if state.ply == new_ply {
             println!("    State did not change since last memento");
-            return;
+            return Vec::new();
         }
 
         // get current board
@@ -148,10 +154,12 @@ impl XmlNode {
                 "RED" => 2,
                 _ => 3,
             };
-            new_board[board_index].flip_bit(x + y * 21);
+            new_board[board_index] |=
+                Bitboard::from(Field::new(x, y).expect("Field is outside of the board"));
         }
 
         // find the actions that lead to the new state and update the GameState
+        let mut actions: Vec<Action> = Vec::new();
         loop {
             let last_board = state.board[state.get_current_color()];
             let changed_fields = new_board[state.get_current_color()] & !last_board;
@@ -166,11 +174,19 @@ impl XmlNode {
                 },
                 action
             );
+            if let Err(reason) = state.check_action(&action) {
+                println!(
+                    "    Warning: The action is illegal in the local state: {}",
+                    reason
+                );
+            }
             state.do_action(action);
+            actions.push(action);
             if state.ply == new_ply {
                 break;
             }
         }
+        actions
     }
 
     pub fn get_children(&self) -> &Vec<XmlNode> {
@@ -178,15 +194,83 @@ impl XmlNode {
     }
 
     pub fn get_child(&self, name: &str) -> Option<&XmlNode> {
-        for child in &self.childs {
-            if child.name.as_str() == name {
-                return Some(child);
-            }
-        }
-        None
+        self.childs.iter().find(|child| child.name.as_str() == name)
     }
 
     pub fn get_attribute(&self, name: &str) -> Option<&String> {
         self.attribs.get(name).map(|a| &a[0])
     }
 }
+
+impl XmlElement for XmlNode {
+    fn attribute(&self, name: &str) -> Option<&str> {
+        self.get_attribute(name).map(|value| value.as_str())
+    }
+
+    fn child(&self, name: &str) -> Option<&Self> {
+        self.get_child(name)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::XmlNode;
+    use game_sdk::{Action, XmlMoveError};
+    use xml::reader::EventReader;
+
+    fn parse(xml: &str) -> XmlNode {
+        // The server wraps every message in a room element, read_from returns its child
+        XmlNode::read_from(&mut EventReader::new(xml.as_bytes()))
+    }
+
+    #[test]
+    fn test_move_from_xml() {
+        for shape in 0..91 {
+            for color in 0..4 {
+                let action = Action::set(5 + 11 * 21, shape);
+                let xml = format!("<room roomId='abc'>{}</room>", action.to_xml(color));
+                assert_eq!(parse(&xml).as_move(), Ok((action, Some(color))));
+            }
+        }
+        let xml = format!("<room roomId='abc'>{}</room>", Action::SKIP.to_xml(2));
+        assert_eq!(parse(&xml).as_move(), Ok((Action::SKIP, None)));
+        let xml =
+            "<room roomId='abc'>\n<data class='sc.plugin2021.SkipMove' color='RED'/>\n</room>";
+        assert_eq!(parse(xml).as_move(), Ok((Action::SKIP, Some(2))));
+        let xml = "<room roomId=\"abc\"><data class=\"sc.plugin2021.SetMove\"><piece color=\"GREEN\" kind=\"PENTO_L\" rotation=\"RIGHT\" isFlipped=\"true\"><position x=\"3\" y=\"7\"/></piece></data></room>";
+        assert_eq!(
+            parse(xml).as_move(),
+            Ok((Action::from_notation("L5:RIGHT:F@3,7").unwrap(), Some(3)))
+        );
+        assert_eq!(
+            parse(&xml.replace("PENTO_L", "PENTO_Q")).as_move(),
+            Err(XmlMoveError::InvalidAttribute(
+                "kind",
+                "PENTO_Q".to_string()
+            ))
+        );
+        assert_eq!(
+            parse(&xml.replace(" isFlipped=\"true\"", "")).as_move(),
+            Err(XmlMoveError::MissingAttribute("isFlipped"))
+        );
+        assert_eq!(
+            parse(&xml.replace("<position x=\"3\" y=\"7\"/>", "")).as_move(),
+            Err(XmlMoveError::MissingElement("position"))
+        );
+        assert_eq!(
+            parse(&xml.replace("x=\"3\"", "x=\"20\"")).as_move(),
+            Err(XmlMoveError::InvalidAttribute("x", "20".to_string()))
+        );
+        assert_eq!(
+            parse(&xml.replace("x=\"3\"", "x=\"19\"")).as_move(),
+            Err(XmlMoveError::OutsideOfBoard(19, 7))
+        );
+        assert_eq!(
+            parse(&xml.replace("SetMove", "JumpMove")).as_move(),
+            Err(XmlMoveError::UnknownMoveClass(
+                "sc.plugin2021.JumpMove".to_string()
+            ))
+        );
+        assert_eq!(parse("<room/>").as_move(), Err(XmlMoveError::MissingMove));
+    }
+}
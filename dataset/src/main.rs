@@ -17,7 +17,13 @@ fn main() {
     }
     let mut mcts = Mcts::default();
     mcts.set_iteration_limit(iterations);
-    let state = GameState::from_fen(fen);
+    let state = match GameState::try_from_fen(&fen) {
+        Ok(state) => state,
+        Err(error) => {
+            println!("Invalid fen \"{}\": {}", fen, error);
+            return;
+        }
+    };
     println!("{}", state);
     let action = mcts.search_action(&state);
     let mut string = "".to_string();
@@ -1,4 +1,6 @@
-use super::{Bitboard, PieceType};
+use super::bitboard::PIECE_SHAPES;
+use super::piece_type::FROM_SHAPE;
+use super::{Bitboard, PieceType, Symmetry};
 use std::fmt::{Display, Formatter, Result};
 
 // There are two types of actions: Set and Skip.
@@ -6,11 +8,124 @@ use std::fmt::{Display, Formatter, Result};
 // The action doesn't store the color of the piece because can be derived from the ply.
 // The destination refers to the top left corner of the piece.
 
-#[derive(Copy, Clone, PartialEq, Debug)]
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub struct Action(u16);
 
+// Rotations as used by the Software-Challenge Server.
+// The server rotates the piece clockwise first and flips it horizontally afterwards.
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Rotation {
+    None = 0,
+    Right = 1,  // 90 degrees clockwise
+    Mirror = 2, // 180 degrees
+    Left = 3,   // 90 degrees counterclockwise
+}
+
+pub const ROTATIONS: [Rotation; 4] = [
+    Rotation::None,
+    Rotation::Right,
+    Rotation::Mirror,
+    Rotation::Left,
+];
+
+impl Rotation {
+    pub fn from_xml_name(name: &str) -> Option<Self> {
+        ROTATIONS
+            .iter()
+            .find(|rotation| rotation.to_xml_name() == name)
+            .copied()
+    }
+
+    pub fn to_xml_name(self) -> &'static str {
+        match self {
+            Rotation::None => "NONE",
+            Rotation::Right => "RIGHT",
+            Rotation::Mirror => "MIRROR",
+            Rotation::Left => "LEFT",
+        }
+    }
+
+    fn to_symmetry(self) -> Symmetry {
+        match self {
+            Rotation::None => Symmetry::Identity,
+            Rotation::Right => Symmetry::RotateRight,
+            Rotation::Mirror => Symmetry::Rotate180,
+            Rotation::Left => Symmetry::RotateLeft,
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ActionNotationError {
+    InvalidFormat(String), // The notation is not "<piece>:<rotation>[:F]@<x>,<y>" or "Skip"
+    UnknownPieceType(String), // Short name that doesn't belong to any piece type
+    UnknownRotation(String), // Rotation that is not NONE, RIGHT, MIRROR or LEFT
+    InvalidCoordinates(String), // Coordinates that are not on the board
+}
+
+impl Display for ActionNotationError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ActionNotationError::InvalidFormat(notation) => {
+                write!(f, "Invalid action notation \"{}\"", notation)
+            }
+            ActionNotationError::UnknownPieceType(name) => {
+                write!(f, "Unknown piece type \"{}\"", name)
+            }
+            ActionNotationError::UnknownRotation(name) => {
+                write!(f, "Unknown rotation \"{}\"", name)
+            }
+            ActionNotationError::InvalidCoordinates(coordinates) => {
+                write!(f, "Invalid coordinates \"{}\"", coordinates)
+            }
+        }
+    }
+}
+
+impl std::error::Error for ActionNotationError {}
+
+// Names of the colors in the XML protocol (indexed by color)
+pub const XML_COLOR_NAMES: [&str; 4] = ["BLUE", "YELLOW", "RED", "GREEN"];
+
+// Read access to an element of a parsed XML message.
+// Moves are read through this trait, so that game_sdk doesn't depend on an XML library.
+pub trait XmlElement {
+    fn attribute(&self, name: &str) -> Option<&str>;
+    fn child(&self, name: &str) -> Option<&Self>;
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum XmlMoveError {
+    MissingMove,                            // The element has no class attribute
+    UnknownMoveClass(String), // The class of the data element is not SetMove or SkipMove
+    MissingElement(&'static str), // The SetMove has no piece or position element
+    MissingAttribute(&'static str), // A required attribute is missing
+    InvalidAttribute(&'static str, String), // Name and value of an attribute that can't be parsed
+    OutsideOfBoard(u16, u16), // The piece doesn't fit on the board at the position (x, y)
+}
+
+impl Display for XmlMoveError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            XmlMoveError::MissingMove => write!(f, "The XML element is not a move"),
+            XmlMoveError::UnknownMoveClass(class) => write!(f, "Unknown move class \"{}\"", class),
+            XmlMoveError::MissingElement(name) => write!(f, "Missing element \"{}\"", name),
+            XmlMoveError::MissingAttribute(name) => write!(f, "Missing attribute \"{}\"", name),
+            XmlMoveError::InvalidAttribute(name, value) => {
+                write!(f, "Invalid value of attribute \"{}\": \"{}\"", name, value)
+            }
+            XmlMoveError::OutsideOfBoard(x, y) => {
+                write!(f, "The piece doesn't fit on the board at ({}, {})", x, y)
+            }
+        }
+    }
+}
+
+impl std::error::Error for XmlMoveError {}
+
 impl Action {
-    pub const SKIP: Self = Self(std::u16::MAX);
+    pub const SKIP: Self = Self(u16::MAX);
 
     #[inline(always)]
     pub fn set(destination: u16, shape: u16) -> Self {
@@ -50,21 +165,12 @@ impl Action {
         if board.is_empty() {
             return Self::SKIP;
         }
-        let mut board_copy = board;
         // Find the top left corner of the piece
         let mut left = 21;
         let mut top = 21;
-        while board_copy.not_empty() {
-            let field_index = board_copy.trailing_zeros();
-            board_copy.flip_bit(field_index);
-            let x = field_index % 21;
-            let y = (field_index - x) / 21;
-            if x < left {
-                left = x;
-            }
-            if y < top {
-                top = y;
-            }
+        for field in board.fields() {
+            left = left.min(field.x());
+            top = top.min(field.y());
         }
         let destination = left + top * 21;
         // Determine the shape of the piece
@@ -74,14 +180,164 @@ impl Action {
             }
         }
         if cfg!(debug_assertions) {
-            println!(
-                "Can't determine action from bitboard.\n{}",
-                board.to_string()
-            );
+            println!("Can't determine action from bitboard.\n{}", board);
         }
         Self::SKIP
     }
 
+    pub fn from_placement(
+        piece_type: PieceType,
+        rotation: Rotation,
+        flipped: bool,
+        x: u16,
+        y: u16,
+    ) -> Option<Self> {
+        // Creates the action that places the piece type in the given orientation with its top left corner at (x, y).
+        // Returns None if the piece doesn't fit on the board at (x, y).
+        let shape = (0..PIECE_ORIENTATIONS.len())
+            .find(|shape| {
+                FROM_SHAPE[*shape] == piece_type && PIECE_ORIENTATIONS[*shape] == (0, false)
+            })
+            .expect("Every piece type has an unrotated shape") as u16;
+        let mut action = rotation.to_symmetry().transform_action(Self::set(0, shape));
+        if flipped {
+            action = Symmetry::Mirror.transform_action(action);
+        }
+        let (width, height) = shape_size(action.get_shape() as usize);
+        if x + width > 20 || y + height > 20 {
+            return None;
+        }
+        Some(Self::set(x + y * 21, action.get_shape()))
+    }
+
+    pub fn placement(self) -> Option<(PieceType, Rotation, bool, u16, u16)> {
+        // Returns the piece type, rotation, flipped, x and y of the action (None for SKIP).
+        // Symmetric pieces have several orientations; the one that is used by the server is returned.
+        if self.is_skip() {
+            return None;
+        }
+        let destination = self.get_destination();
+        let shape = self.get_shape() as usize;
+        let (rotation, flipped) = PIECE_ORIENTATIONS[shape];
+        Some((
+            PieceType::from_shape(shape),
+            ROTATIONS[rotation as usize],
+            flipped,
+            destination % 21,
+            destination / 21,
+        ))
+    }
+
+    pub fn to_notation(self) -> String {
+        // Compact notation of the action, for example "L5:RIGHT:F@3,7" or "Skip"
+        match self.placement() {
+            None => "Skip".to_string(),
+            Some((piece_type, rotation, flipped, x, y)) => format!(
+                "{}:{}{}@{},{}",
+                piece_type.to_short_name(),
+                rotation.to_xml_name(),
+                if flipped { ":F" } else { "" },
+                x,
+                y
+            ),
+        }
+    }
+
+    pub fn from_notation(notation: &str) -> std::result::Result<Self, ActionNotationError> {
+        // The rotation is optional (NONE) and ":F" can be appended to flip the piece
+        let notation = notation.trim();
+        let invalid_format = || ActionNotationError::InvalidFormat(notation.to_string());
+        if notation.eq_ignore_ascii_case("skip") {
+            return Ok(Self::SKIP);
+        }
+        let (piece, coordinates) = notation.split_once('@').ok_or_else(invalid_format)?;
+        let mut entries = piece.split(':');
+        let name = entries.next().ok_or_else(invalid_format)?;
+        let piece_type = PieceType::from_short_name(name)
+            .ok_or_else(|| ActionNotationError::UnknownPieceType(name.to_string()))?;
+        let rotation = match entries.next() {
+            Some(name) => Rotation::from_xml_name(name)
+                .ok_or_else(|| ActionNotationError::UnknownRotation(name.to_string()))?,
+            None => Rotation::None,
+        };
+        let flipped = match entries.next() {
+            Some("F") => true,
+            Some(_) => return Err(invalid_format()),
+            None => false,
+        };
+        if entries.next().is_some() {
+            return Err(invalid_format());
+        }
+        let invalid_coordinates =
+            || ActionNotationError::InvalidCoordinates(coordinates.to_string());
+        let (x, y) = coordinates
+            .split_once(',')
+            .ok_or_else(invalid_coordinates)?;
+        let x = x.trim().parse::<u16>().map_err(|_| invalid_coordinates())?;
+        let y = y.trim().parse::<u16>().map_err(|_| invalid_coordinates())?;
+        Self::from_placement(piece_type, rotation, flipped, x, y).ok_or_else(invalid_coordinates)
+    }
+
+    pub fn from_xml<E: XmlElement>(
+        element: &E,
+    ) -> std::result::Result<(Self, Option<usize>), XmlMoveError> {
+        // Reads a move element (the data element of to_xml) and returns the action and the color of the move.
+        // The color is optional for SkipMoves.
+        let color = |element: &E| match element.attribute("color") {
+            Some(name) => XML_COLOR_NAMES
+                .iter()
+                .position(|color| *color == name)
+                .map(Some)
+                .ok_or_else(|| XmlMoveError::InvalidAttribute("color", name.to_string())),
+            None => Ok(None),
+        };
+        match element
+            .attribute("class")
+            .ok_or(XmlMoveError::MissingMove)?
+        {
+            "sc.plugin2021.SkipMove" => Ok((Self::SKIP, color(element)?)),
+            "sc.plugin2021.SetMove" => {
+                let piece = element
+                    .child("piece")
+                    .ok_or(XmlMoveError::MissingElement("piece"))?;
+                let position = piece
+                    .child("position")
+                    .ok_or(XmlMoveError::MissingElement("position"))?;
+                let get = |element: &E, name: &'static str| {
+                    element
+                        .attribute(name)
+                        .map(|value| value.to_string())
+                        .ok_or(XmlMoveError::MissingAttribute(name))
+                };
+                let color = color(piece)?.ok_or(XmlMoveError::MissingAttribute("color"))?;
+                let kind = get(piece, "kind")?;
+                let piece_type = PieceType::from_xml_name(&kind)
+                    .ok_or(XmlMoveError::InvalidAttribute("kind", kind))?;
+                let rotation = get(piece, "rotation")?;
+                let rotation = Rotation::from_xml_name(&rotation)
+                    .ok_or(XmlMoveError::InvalidAttribute("rotation", rotation))?;
+                let flipped = get(piece, "isFlipped")?;
+                let flipped = match flipped.as_ref() {
+                    "true" => true,
+                    "false" => false,
+                    _ => return Err(XmlMoveError::InvalidAttribute("isFlipped", flipped)),
+                };
+                let coordinate = |name: &'static str| {
+                    let value = get(position, name)?;
+                    match value.parse::<u16>() {
+                        Ok(coordinate) if coordinate < 20 => Ok(coordinate),
+                        _ => Err(XmlMoveError::InvalidAttribute(name, value)),
+                    }
+                };
+                let (x, y) = (coordinate("x")?, coordinate("y")?);
+                let action = Self::from_placement(piece_type, rotation, flipped, x, y)
+                    .ok_or(XmlMoveError::OutsideOfBoard(x, y))?;
+                Ok((action, Some(color)))
+            }
+            class => Err(XmlMoveError::UnknownMoveClass(class.to_string())),
+        }
+    }
+
     pub fn to_xml(self, color: usize) -> String {
         if self.is_skip() {
             "<data class=\"sc.plugin2021.SkipMove\"/>".to_string()
@@ -89,27 +345,18 @@ impl Action {
             let destination = self.get_destination();
             let shape = self.get_shape() as usize;
             let piece_type = PieceType::from_shape(shape);
-            let (r, flipped) = PIECE_ORIENTATIONS[shape];
-            let rotation = match r {
-                0 => "NONE".to_string(),
-                1 => "RIGHT".to_string(),
-                2 => "MIRROR".to_string(),
-                _ => "LEFT".to_string(),
-            };
+            let (rotation, flipped) = PIECE_ORIENTATIONS[shape];
+            let rotation = ROTATIONS[rotation as usize].to_xml_name();
             let x = destination % 21;
             let y = (destination - x) / 21;
             let mut xml =
                 "  <data class=\"sc.plugin2021.SetMove\">\n    <piece color=\"".to_string();
-            xml.push_str(match color as u8 {
-                0 => "BLUE\" ",
-                1 => "YELLOW\" ",
-                2 => "RED\" ",
-                _ => "GREEN\" ",
-            });
+            xml.push_str(XML_COLOR_NAMES[color & 0b11]);
+            xml.push_str("\" ");
             xml.push_str(&format!(
                 "kind=\"{}\" rotation=\"{}\" isFlipped=\"",
                 &piece_type.to_xml_name(),
-                &rotation,
+                rotation,
             ));
             xml.push_str(&format!(
                 "{}\">\n      <position x=\"{}\" y=\"{}\"/>\n    </piece>\n  </data>",
@@ -139,7 +386,7 @@ impl Action {
         } else {
             Bitboard::empty()
         };
-        format!("{}\n{}", self.to_string(), board)
+        format!("{}\n{}", self, board)
     }
 }
 
@@ -165,6 +412,19 @@ impl Display for Action {
     }
 }
 
+fn shape_size(shape: usize) -> (u16, u16) {
+    // Returns the width and the height of the shape
+    let mut shape_board = PIECE_SHAPES[shape];
+    let (mut width, mut height) = (0, 0);
+    while shape_board != 0 {
+        let bit = shape_board.trailing_zeros() as u16;
+        shape_board ^= 1 << bit;
+        width = width.max(bit % 21 + 1);
+        height = height.max(bit / 21 + 1);
+    }
+    (width, height)
+}
+
 // This array maps every shape to the orientation that is used by the Software-Challenge Server
 // rotation, flipped
 const PIECE_ORIENTATIONS: [(u8, bool); 91] = [
@@ -30,35 +30,17 @@ impl ActionList {
     }
 
     #[inline(always)]
-    pub fn append(&mut self, mut destinations: Bitboard, shape: u16) {
-        while destinations.0 != 0 {
-            let d = destinations.0.trailing_zeros();
-            destinations.0 ^= 1 << d;
-            self.push(Action::set(d as u16 + 384, shape));
-        }
-        while destinations.1 != 0 {
-            let d = destinations.1.trailing_zeros();
-            destinations.1 ^= 1 << d;
-            self.push(Action::set(d as u16 + 256, shape));
-        }
-        while destinations.2 != 0 {
-            let d = destinations.2.trailing_zeros();
-            destinations.2 ^= 1 << d;
-            self.push(Action::set(d as u16 + 128, shape));
-        }
-        while destinations.3 != 0 {
-            let d = destinations.3.trailing_zeros();
-            destinations.3 ^= 1 << d;
-            self.push(Action::set(d as u16, shape));
-        }
+    pub fn append(&mut self, destinations: Bitboard, shape: u16) {
+        destinations.for_each_bit(|destination| self.push(Action::set(destination, shape)));
     }
 }
 
 impl Default for ActionList {
     fn default() -> Self {
-        #[allow(clippy::uninit_assumed_init)]
-        let actions = unsafe { std::mem::MaybeUninit::uninit().assume_init() };
-        Self { actions, size: 0 }
+        Self {
+            actions: [Action::SKIP; MAX_ACTIONS],
+            size: 0,
+        }
     }
 }
 
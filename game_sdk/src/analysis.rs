@@ -0,0 +1,216 @@
+use super::bitboard::PIECE_SHAPES;
+use super::{Bitboard, GameState, PieceType, PIECE_TYPES, VALID_FIELDS};
+
+// Territory analysis that is shared by the evaluations, the move ordering heuristics and analysis tools.
+//
+// A color can cover a field if it is empty and doesn't share an edge with a piece of the color.
+// The reachable region of a color is built from placements of its remaining pieces: A placement is
+// reachable if it only covers such fields and touches a placement field of the color or a corner of
+// another reachable placement. The pieces of the other colors that will be placed later are ignored,
+// and so is the fact that the pieces of one chain of placements can't touch each other by edges.
+// The team regions and the empty areas are built from these regions.
+
+pub struct EmptyArea {
+    pub fields: Bitboard,
+    pub size: u32,
+    pub reachable_by: [bool; 4], // Colors whose reachable region contains a field of the area
+}
+
+pub struct TerritoryAnalysis {
+    pub placement_fields: [Bitboard; 4], // Fields at which each color can place its next piece
+    pub reachable_fields: [Bitboard; 4], // Fields that each color can reach (see reachable_fields)
+    pub exclusive_fields: [Bitboard; 2], // Fields that only one team can reach (Blue/Red, Yellow/Green)
+    pub contested_fields: Bitboard,      // Fields that both teams can reach
+    pub empty_areas: Vec<EmptyArea>,     // Groups of empty fields that are separated by pieces
+}
+
+pub fn placement_fields(state: &GameState, color: usize) -> Bitboard {
+    // Returns all fields at which the color can place its next piece
+    let can_place = !state.has_color_skipped(color)
+        && (0..21).any(|piece_type| state.pieces_left[piece_type][color]);
+    if !can_place {
+        return Bitboard::empty();
+    }
+    state.placement_fields(color)
+}
+
+pub fn expand(region: Bitboard, free: Bitboard, steps: usize) -> Bitboard {
+    // Grows the region over edges into the free fields
+    let mut region = region;
+    for _ in 0..steps {
+        region |= region.neighbors() & free;
+    }
+    region
+}
+
+pub fn reachable_fields(state: &GameState, color: usize) -> Bitboard {
+    // Returns all fields that the color could cover if no other pieces were placed
+    reachable_placements(state, color).0
+}
+
+fn reachable_placements(state: &GameState, color: usize) -> (Bitboard, [bool; 21]) {
+    // Adds the placements that touch new corners until no new corners are found.
+    // Returns the covered fields and which piece types (indexed by piece type) have a reachable placement.
+    let legal_fields = state.legal_fields(color);
+    let mut reachable = Bitboard::empty();
+    let mut placeable = [false; 21];
+    let mut shapes: Vec<(usize, Bitboard, Bitboard)> = Vec::new();
+    for shape in 0..PIECE_SHAPES.len() {
+        if state.pieces_left[PieceType::from_shape(shape) as usize][color] {
+            let fitting = fitting_destinations(legal_fields, shape);
+            if fitting.not_empty() {
+                shapes.push((shape, fitting, Bitboard::empty()));
+            }
+        }
+    }
+    // In the first turn of the color only the required piece type can be placed
+    let mut required = state.required_piece_type(color);
+    let mut corners = placement_fields(state, color);
+    let mut new_corners = corners;
+    while new_corners.not_empty() {
+        let mut next_corners = Bitboard::empty();
+        for (shape, fitting, placed) in shapes.iter_mut() {
+            let piece_type = PieceType::from_shape(*shape);
+            if required.is_some() && required != Some(piece_type) {
+                continue;
+            }
+            let destinations = *fitting & covering_destinations(new_corners, *shape) & !*placed;
+            if destinations.is_empty() {
+                continue;
+            }
+            *placed |= destinations;
+            placeable[piece_type as usize] = true;
+            reachable |= placed_fields(destinations, *shape);
+            next_corners |= placed_corners(destinations, *shape);
+        }
+        required = None;
+        new_corners = next_corners & legal_fields & !corners;
+        corners |= new_corners;
+    }
+    (reachable, placeable)
+}
+
+pub fn empty_areas(state: &GameState) -> Vec<Bitboard> {
+    // Splits the empty fields into groups that are connected by edges
+    let mut empty = !state.get_occupied_fields() & VALID_FIELDS;
+    let mut areas: Vec<Bitboard> = Vec::new();
+    while empty.not_empty() {
+        let mut area = Bitboard::bit(empty.trailing_zeros());
+        loop {
+            let grown = area | (area.neighbors() & empty);
+            if grown == area {
+                break;
+            }
+            area = grown;
+        }
+        empty ^= area;
+        areas.push(area);
+    }
+    areas
+}
+
+pub fn analyze_territory(state: &GameState) -> TerritoryAnalysis {
+    let mut placement = [Bitboard::empty(); 4];
+    let mut reachable = [Bitboard::empty(); 4];
+    for color in 0..4 {
+        placement[color] = placement_fields(state, color);
+        reachable[color] = reachable_fields(state, color);
+    }
+    let team_one = reachable[0] | reachable[2];
+    let team_two = reachable[1] | reachable[3];
+    let empty_areas = empty_areas(state)
+        .into_iter()
+        .map(|fields| {
+            let mut reachable_by = [false; 4];
+            for color in 0..4 {
+                reachable_by[color] = (fields & reachable[color]).not_empty();
+            }
+            EmptyArea {
+                fields,
+                size: fields.count_ones(),
+                reachable_by,
+            }
+        })
+        .collect();
+    TerritoryAnalysis {
+        placement_fields: placement,
+        reachable_fields: reachable,
+        exclusive_fields: [team_one & !team_two, team_two & !team_one],
+        contested_fields: team_one & team_two,
+        empty_areas,
+    }
+}
+
+pub fn fitting_destinations(region: Bitboard, shape: usize) -> Bitboard {
+    // Returns all destinations at which the shape only covers fields of the region
+    let mut destinations = VALID_FIELDS;
+    let mut shape_board = PIECE_SHAPES[shape];
+    while shape_board != 0 {
+        let bit = shape_board.trailing_zeros() as usize;
+        shape_board ^= 1 << bit;
+        destinations &= region.r_shift_save(bit);
+    }
+    destinations
+}
+
+pub fn covering_destinations(fields: Bitboard, shape: usize) -> Bitboard {
+    // Returns all destinations at which the shape covers at least one of the fields
+    let mut destinations = Bitboard::empty();
+    let mut shape_board = PIECE_SHAPES[shape];
+    while shape_board != 0 {
+        let bit = shape_board.trailing_zeros() as usize;
+        shape_board ^= 1 << bit;
+        destinations |= fields.r_shift_save(bit);
+    }
+    destinations
+}
+
+fn placed_fields(destinations: Bitboard, shape: usize) -> Bitboard {
+    // Returns all fields that the shape covers at one of the destinations
+    let mut fields = Bitboard::empty();
+    let mut shape_board = PIECE_SHAPES[shape];
+    while shape_board != 0 {
+        let bit = shape_board.trailing_zeros() as usize;
+        shape_board ^= 1 << bit;
+        fields |= destinations.l_shift_save(bit);
+    }
+    fields
+}
+
+fn placed_corners(destinations: Bitboard, shape: usize) -> Bitboard {
+    // Returns the fields that touch the shape at one of the destinations only by a corner.
+    // The corners of the shape are taken from a copy at the second field of the second row,
+    // because corners above or to the left of the destination have negative offsets.
+    const ORIGIN: usize = 22;
+    let piece = Bitboard::with_piece(ORIGIN as u16, shape);
+    let mut shape_corners = piece.diagonal_neighbors() & !(piece | piece.neighbors());
+    let mut corners = Bitboard::empty();
+    while shape_corners.not_empty() {
+        let field = shape_corners.trailing_zeros() as usize;
+        shape_corners.flip_bit(field as u16);
+        corners |= if field >= ORIGIN {
+            destinations.l_shift_save(field - ORIGIN)
+        } else {
+            destinations.r_shift_save(ORIGIN - field)
+        };
+    }
+    // Corners that were shifted across the edge of the board end up in the padding column
+    corners & VALID_FIELDS
+}
+
+pub fn placeable_piece_types(state: &GameState, color: usize) -> [bool; 21] {
+    // Returns which of the remaining piece types (indexed by piece type) have a reachable placement.
+    // The other piece types can never be placed. Piece types with a placement might still be blocked later.
+    reachable_placements(state, color).1
+}
+
+pub fn max_additional_fields(state: &GameState, color: usize) -> u32 {
+    // Upper bound of the number of fields that the color can cover until the end of the game
+    let (reachable, placeable) = reachable_placements(state, color);
+    let piece_fields: u32 = PIECE_TYPES
+        .iter()
+        .filter(|piece_type| placeable[**piece_type as usize])
+        .map(|piece_type| piece_type.piece_size() as u32)
+        .sum();
+    piece_fields.min(reachable.count_ones())
+}
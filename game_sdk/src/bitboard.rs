@@ -1,8 +1,17 @@
-use super::Action;
+use super::{Action, FieldIter};
 use rand::{rngs::SmallRng, RngCore};
 use std::fmt::{Display, Formatter, Result};
-use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
-use std::ops::{Shl, ShlAssign, Shr, ShrAssign};
+
+// The representation of the 512-bit bitboards is chosen with the "simd" feature.
+// Both backends provide the same primitive operations and produce identical results.
+#[cfg(not(feature = "simd"))]
+mod u128x4;
+#[cfg(feature = "simd")]
+mod u64x8;
+#[cfg(not(feature = "simd"))]
+pub use u128x4::Bitboard;
+#[cfg(feature = "simd")]
+pub use u64x8::Bitboard;
 
 /* VALID_FIELDS
        X   0     1     2     3     4     5     6     7     8     9    10    11    12    13    14    15    16    17    18    19
@@ -48,12 +57,12 @@ use std::ops::{Shl, ShlAssign, Shr, ShrAssign};
          --------------------------------------------------------------------------------------------------------------------------
     19   399 | 400 | 401 | 402 | 403 | 404 | 405 | 406 | 407 | 408 | 409 | 410 | 411 | 412 | 413 | 414 | 415 | 416 | 417 | 418 | .
 */
-pub const VALID_FIELDS: Bitboard = Bitboard(
+pub const VALID_FIELDS: Bitboard = Bitboard::from_u128s([
     34359721983,
     337623909661717427026139553986326233087,
     329648537884054317714434393650000297983,
     297747050773401880467613752304696557567,
-);
+]);
 
 /* COLUMN_MASK
     1  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
@@ -77,12 +86,12 @@ pub const VALID_FIELDS: Bitboard = Bitboard(
     1  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
     1  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
 */
-pub const COLUMN_MASK: Bitboard = Bitboard(
+pub const COLUMN_MASK: Bitboard = Bitboard::from_u128s([
     32768,
     5316914518442072874470106890883956736,
     21267658073768291497880427563535826944,
     85070632295073165991521710254143307777,
-);
+]);
 
 /* ROW_MASK
     1  1  1  1  1  1  1  1  1  1  1  1  1  1  1  1  1  1  1  1
@@ -106,7 +115,7 @@ pub const COLUMN_MASK: Bitboard = Bitboard(
     .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
     .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
 */
-pub const ROW_MASK: Bitboard = Bitboard(0, 0, 0, 1048575);
+pub const ROW_MASK: Bitboard = Bitboard::from_u128s([0, 0, 0, 1048575]);
 
 /* START_FIELDS
     1  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  1
@@ -130,80 +139,39 @@ pub const ROW_MASK: Bitboard = Bitboard(0, 0, 0, 1048575);
     .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
     1  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  1
 */
-pub const START_FIELDS: Bitboard = Bitboard(1 << 34 | 1 << 15, 0, 0, 1 | 1 << 19);
-
-#[derive(Debug, Copy, Clone, Eq)]
-pub struct Bitboard(pub u128, pub u128, pub u128, pub u128);
-
-impl Bitboard {
-    pub fn empty() -> Self {
-        Bitboard(0, 0, 0, 0)
-    }
-
-    pub fn with_piece(destination: u16, shape: usize) -> Bitboard {
-        let shape = PIECE_SHAPES[shape];
-        let shift = destination as u8 & 0b1111111;
-        let board = match destination >> 7 {
-            0 => Bitboard(0, 0, 0, shape),
-            1 => Bitboard(0, 0, shape, 0),
-            2 => Bitboard(0, shape, 0, 0),
-            _ => Bitboard(shape, 0, 0, 0),
-        };
-        if shift != 0 {
-            board << shift
-        } else {
-            board
-        }
-    }
-
-    pub fn bit(bit_idx: u16) -> Bitboard {
-        if bit_idx < 128 {
-            Bitboard(0, 0, 0, 1 << bit_idx)
-        } else if bit_idx < 256 {
-            Bitboard(0, 0, 1 << (bit_idx - 128), 0)
-        } else if bit_idx < 384 {
-            Bitboard(0, 1 << (bit_idx - 256), 0, 0)
-        } else {
-            Bitboard(1 << (bit_idx - 384), 0, 0, 0)
-        }
-    }
-
-    pub fn check_bit(&self, bit_idx: u16) -> bool {
-        if bit_idx < 128 {
-            self.3 & 1 << bit_idx != 0
-        } else if bit_idx < 256 {
-            self.2 & 1 << (bit_idx - 128) != 0
-        } else if bit_idx < 384 {
-            self.1 & 1 << (bit_idx - 256) != 0
-        } else {
-            self.0 & 1 << (bit_idx - 384) != 0
+pub const START_FIELDS: Bitboard = Bitboard::from_u128s([1 << 34 | 1 << 15, 0, 0, 1 | 1 << 19]);
+
+// DIAGONAL_MASKS[19 + d] contains the fields (x, y) with x - y = d
+const DIAGONAL_MASKS: [Bitboard; 39] = diagonal_masks();
+
+const fn diagonal_masks() -> [Bitboard; 39] {
+    let mut masks = [Bitboard::from_u128s([0; 4]); 39];
+    let mut d = 0;
+    while d < 39 {
+        let mut parts: [u128; 4] = [0; 4];
+        let mut y = 0;
+        while y < 20 {
+            let x = y + d;
+            if x >= 19 && x < 39 {
+                let bit = x - 19 + y * 21;
+                parts[3 - bit / 128] |= 1 << (bit % 128);
+            }
+            y += 1;
         }
+        masks[d] = Bitboard::from_u128s(parts);
+        d += 1;
     }
+    masks
+}
 
-    pub fn flip_bit(&mut self, bit_idx: u16) {
-        if bit_idx < 128 {
-            self.3 ^= 1 << bit_idx;
-        } else if bit_idx < 256 {
-            self.2 ^= 1 << (bit_idx - 128);
-        } else if bit_idx < 384 {
-            self.1 ^= 1 << (bit_idx - 256);
-        } else {
-            self.0 ^= 1 << (bit_idx - 384);
-        }
+impl Bitboard {
+    pub fn with_piece(destination: u16, shape: usize) -> Bitboard {
+        Bitboard::with_fields(destination, PIECE_SHAPES[shape])
     }
 
-    pub fn trailing_zeros(&self) -> u16 {
-        if self.3 != 0 {
-            self.3.trailing_zeros() as u16
-        } else if self.2 != 0 {
-            self.2.trailing_zeros() as u16 + 128
-        } else if self.1 != 0 {
-            self.1.trailing_zeros() as u16 + 256
-        } else if self.0 != 0 {
-            self.0.trailing_zeros() as u16 + 384
-        } else {
-            512
-        }
+    pub fn fields(&self) -> FieldIter {
+        // Iterates over the set fields from the top left to the bottom right
+        FieldIter::new(*self)
     }
 
     pub fn r_shift_save(&self, mut n: usize) -> Bitboard {
@@ -249,13 +217,12 @@ impl Bitboard {
     }
 
     pub fn mirror_diagonal(&self) -> Bitboard {
-        let mut board = Bitboard::empty();
-        for x in 0..20 {
-            for y in 0..20 {
-                if self.check_bit((x + y * 21) as u16) {
-                    board.flip_bit((y + x * 21) as u16);
-                }
-            }
+        // The field (x, y) moves to (y, x), which is 20 * (x - y) bits further,
+        // so every diagonal with a constant x - y is moved with a single shift
+        let mut board = *self & DIAGONAL_MASKS[19];
+        for d in 1..20 {
+            board |= (*self & DIAGONAL_MASKS[19 + d]).l_shift_save(20 * d);
+            board |= (*self & DIAGONAL_MASKS[19 - d]).r_shift_save(20 * d);
         }
         board
     }
@@ -268,21 +235,6 @@ impl Bitboard {
         self.mirror_diagonal().mirror()
     }
 
-    #[inline(always)]
-    pub fn count_ones(&self) -> u32 {
-        self.0.count_ones() + self.1.count_ones() + self.2.count_ones() + self.3.count_ones()
-    }
-
-    #[inline(always)]
-    pub fn is_empty(&self) -> bool {
-        self.0 == 0 && self.1 == 0 && self.2 == 0 && self.3 == 0
-    }
-
-    #[inline(always)]
-    pub fn not_empty(&self) -> bool {
-        self.0 != 0 || self.1 != 0 || self.2 != 0 || self.3 != 0
-    }
-
     #[inline(always)]
     pub fn neighbors(&self) -> Bitboard {
         ((*self << 1) | (*self >> 1) | (*self >> 21) | (*self << 21)) & VALID_FIELDS
@@ -319,135 +271,8 @@ impl Bitboard {
     }
 
     pub fn to_fen(&self) -> String {
-        format!("{} {} {} {}", self.0, self.1, self.2, self.3)
-    }
-}
-
-impl BitXor for Bitboard {
-    type Output = Self;
-
-    fn bitxor(self, other: Self) -> Self::Output {
-        Bitboard(
-            self.0 ^ other.0,
-            self.1 ^ other.1,
-            self.2 ^ other.2,
-            self.3 ^ other.3,
-        )
-    }
-}
-
-impl BitXorAssign for Bitboard {
-    fn bitxor_assign(&mut self, other: Self) {
-        self.0 ^= other.0;
-        self.1 ^= other.1;
-        self.2 ^= other.2;
-        self.3 ^= other.3;
-    }
-}
-
-impl BitAnd for Bitboard {
-    type Output = Self;
-
-    fn bitand(self, other: Self) -> Self::Output {
-        Bitboard(
-            self.0 & other.0,
-            self.1 & other.1,
-            self.2 & other.2,
-            self.3 & other.3,
-        )
-    }
-}
-
-impl BitAndAssign for Bitboard {
-    fn bitand_assign(&mut self, other: Self) {
-        self.0 &= other.0;
-        self.1 &= other.1;
-        self.2 &= other.2;
-        self.3 &= other.3;
-    }
-}
-
-impl BitOr for Bitboard {
-    type Output = Self;
-
-    fn bitor(self, other: Self) -> Self::Output {
-        Bitboard(
-            self.0 | other.0,
-            self.1 | other.1,
-            self.2 | other.2,
-            self.3 | other.3,
-        )
-    }
-}
-
-impl BitOrAssign for Bitboard {
-    fn bitor_assign(&mut self, other: Self) {
-        self.0 |= other.0;
-        self.1 |= other.1;
-        self.2 |= other.2;
-        self.3 |= other.3;
-    }
-}
-
-impl Not for Bitboard {
-    type Output = Self;
-
-    fn not(self) -> Self::Output {
-        Bitboard(!self.0, !self.1, !self.2, !self.3)
-    }
-}
-
-impl Shl<u8> for Bitboard {
-    type Output = Self;
-
-    fn shl(self, n: u8) -> Self::Output {
-        // 0 < n < 128. Use l_shift_save if n might be 0 or larger than 127
-        Bitboard(
-            (self.0 << n) | (self.1 >> (128 - n)),
-            (self.1 << n) | (self.2 >> (128 - n)),
-            (self.2 << n) | (self.3 >> (128 - n)),
-            self.3 << n,
-        )
-    }
-}
-
-impl ShlAssign<u8> for Bitboard {
-    fn shl_assign(&mut self, n: u8) {
-        // 0 < n < 128. Use l_shift_save if n might be 0 or larger than 127
-        self.0 = (self.0 << n) | (self.1 >> (128 - n));
-        self.1 = (self.1 << n) | (self.2 >> (128 - n));
-        self.2 = (self.2 << n) | (self.3 >> (128 - n));
-        self.3 = self.3 << n;
-    }
-}
-
-impl Shr<u8> for Bitboard {
-    type Output = Self;
-
-    fn shr(self, n: u8) -> Self::Output {
-        // 0 < n < 128. Use r_shift_save if n might be 0 or larger than 127
-        Bitboard(
-            self.0 >> n,
-            (self.1 >> n) | (self.0 << (128 - n)),
-            (self.2 >> n) | (self.1 << (128 - n)),
-            (self.3 >> n) | self.2 << (128 - n),
-        )
-    }
-}
-
-impl ShrAssign<u8> for Bitboard {
-    fn shr_assign(&mut self, n: u8) {
-        // 0 < n < 128. Use r_shift_save if n might be 0 or larger than 127
-        self.3 = (self.3 >> n) | self.2 << (128 - n);
-        self.2 = (self.2 >> n) | (self.1 << (128 - n));
-        self.1 = (self.1 >> n) | (self.0 << (128 - n));
-        self.0 >>= n;
-    }
-}
-
-impl PartialEq for Bitboard {
-    fn eq(&self, other: &Self) -> bool {
-        self.0 == other.0 && self.1 == other.1 && self.2 == other.2 && self.3 == other.3
+        let [a, b, c, d] = self.to_u128s();
+        format!("{} {} {} {}", a, b, c, d)
     }
 }
 
@@ -0,0 +1,249 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::ops::{Shl, ShlAssign, Shr, ShrAssign};
+
+// The default Bitboard backend: four u128 that carry bits across each other by hand.
+// The first u128 stores the most significant bits (384..511), the last one the bits 0..127.
+
+#[derive(Debug, Copy, Clone, Eq)]
+pub struct Bitboard(u128, u128, u128, u128);
+
+impl Bitboard {
+    pub const fn from_u128s(parts: [u128; 4]) -> Self {
+        // The parts are ordered from the most to the least significant bits, like in the fen
+        Bitboard(parts[0], parts[1], parts[2], parts[3])
+    }
+
+    #[inline(always)]
+    pub fn to_u128s(&self) -> [u128; 4] {
+        [self.0, self.1, self.2, self.3]
+    }
+
+    #[inline(always)]
+    pub fn empty() -> Self {
+        Bitboard(0, 0, 0, 0)
+    }
+
+    #[inline(always)]
+    pub(super) fn with_fields(destination: u16, fields: u128) -> Bitboard {
+        // Places the fields (relative to the top left corner) at the destination
+        let shift = destination as u8 & 0b1111111;
+        let board = match destination >> 7 {
+            0 => Bitboard(0, 0, 0, fields),
+            1 => Bitboard(0, 0, fields, 0),
+            2 => Bitboard(0, fields, 0, 0),
+            _ => Bitboard(fields, 0, 0, 0),
+        };
+        if shift != 0 {
+            board << shift
+        } else {
+            board
+        }
+    }
+
+    pub fn bit(bit_idx: u16) -> Bitboard {
+        if bit_idx < 128 {
+            Bitboard(0, 0, 0, 1 << bit_idx)
+        } else if bit_idx < 256 {
+            Bitboard(0, 0, 1 << (bit_idx - 128), 0)
+        } else if bit_idx < 384 {
+            Bitboard(0, 1 << (bit_idx - 256), 0, 0)
+        } else {
+            Bitboard(1 << (bit_idx - 384), 0, 0, 0)
+        }
+    }
+
+    pub fn check_bit(&self, bit_idx: u16) -> bool {
+        if bit_idx < 128 {
+            self.3 & 1 << bit_idx != 0
+        } else if bit_idx < 256 {
+            self.2 & 1 << (bit_idx - 128) != 0
+        } else if bit_idx < 384 {
+            self.1 & 1 << (bit_idx - 256) != 0
+        } else {
+            self.0 & 1 << (bit_idx - 384) != 0
+        }
+    }
+
+    pub fn flip_bit(&mut self, bit_idx: u16) {
+        if bit_idx < 128 {
+            self.3 ^= 1 << bit_idx;
+        } else if bit_idx < 256 {
+            self.2 ^= 1 << (bit_idx - 128);
+        } else if bit_idx < 384 {
+            self.1 ^= 1 << (bit_idx - 256);
+        } else {
+            self.0 ^= 1 << (bit_idx - 384);
+        }
+    }
+
+    pub fn trailing_zeros(&self) -> u16 {
+        if self.3 != 0 {
+            self.3.trailing_zeros() as u16
+        } else if self.2 != 0 {
+            self.2.trailing_zeros() as u16 + 128
+        } else if self.1 != 0 {
+            self.1.trailing_zeros() as u16 + 256
+        } else if self.0 != 0 {
+            self.0.trailing_zeros() as u16 + 384
+        } else {
+            512
+        }
+    }
+
+    #[inline(always)]
+    pub fn for_each_bit<F: FnMut(u16)>(&self, mut f: F) {
+        // Calls f with the index of each set bit.
+        // The bits 384..511 come first, then 256..383, 128..255 and 0..127 (ascending in each part).
+        let mut parts = [(self.0, 384), (self.1, 256), (self.2, 128), (self.3, 0)];
+        for (part, offset) in parts.iter_mut() {
+            while *part != 0 {
+                let bit = part.trailing_zeros() as u16;
+                *part ^= 1 << bit;
+                f(bit + *offset);
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn count_ones(&self) -> u32 {
+        self.0.count_ones() + self.1.count_ones() + self.2.count_ones() + self.3.count_ones()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0 == 0 && self.1 == 0 && self.2 == 0 && self.3 == 0
+    }
+
+    #[inline(always)]
+    pub fn not_empty(&self) -> bool {
+        self.0 != 0 || self.1 != 0 || self.2 != 0 || self.3 != 0
+    }
+}
+
+impl BitXor for Bitboard {
+    type Output = Self;
+
+    fn bitxor(self, other: Self) -> Self::Output {
+        Bitboard(
+            self.0 ^ other.0,
+            self.1 ^ other.1,
+            self.2 ^ other.2,
+            self.3 ^ other.3,
+        )
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    fn bitxor_assign(&mut self, other: Self) {
+        self.0 ^= other.0;
+        self.1 ^= other.1;
+        self.2 ^= other.2;
+        self.3 ^= other.3;
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Self;
+
+    fn bitand(self, other: Self) -> Self::Output {
+        Bitboard(
+            self.0 & other.0,
+            self.1 & other.1,
+            self.2 & other.2,
+            self.3 & other.3,
+        )
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    fn bitand_assign(&mut self, other: Self) {
+        self.0 &= other.0;
+        self.1 &= other.1;
+        self.2 &= other.2;
+        self.3 &= other.3;
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Self;
+
+    fn bitor(self, other: Self) -> Self::Output {
+        Bitboard(
+            self.0 | other.0,
+            self.1 | other.1,
+            self.2 | other.2,
+            self.3 | other.3,
+        )
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    fn bitor_assign(&mut self, other: Self) {
+        self.0 |= other.0;
+        self.1 |= other.1;
+        self.2 |= other.2;
+        self.3 |= other.3;
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Self;
+
+    fn not(self) -> Self::Output {
+        Bitboard(!self.0, !self.1, !self.2, !self.3)
+    }
+}
+
+impl Shl<u8> for Bitboard {
+    type Output = Self;
+
+    fn shl(self, n: u8) -> Self::Output {
+        // 0 < n < 128. Use l_shift_save if n might be 0 or larger than 127
+        Bitboard(
+            (self.0 << n) | (self.1 >> (128 - n)),
+            (self.1 << n) | (self.2 >> (128 - n)),
+            (self.2 << n) | (self.3 >> (128 - n)),
+            self.3 << n,
+        )
+    }
+}
+
+impl ShlAssign<u8> for Bitboard {
+    fn shl_assign(&mut self, n: u8) {
+        // 0 < n < 128. Use l_shift_save if n might be 0 or larger than 127
+        self.0 = (self.0 << n) | (self.1 >> (128 - n));
+        self.1 = (self.1 << n) | (self.2 >> (128 - n));
+        self.2 = (self.2 << n) | (self.3 >> (128 - n));
+        self.3 = self.3 << n;
+    }
+}
+
+impl Shr<u8> for Bitboard {
+    type Output = Self;
+
+    fn shr(self, n: u8) -> Self::Output {
+        // 0 < n < 128. Use r_shift_save if n might be 0 or larger than 127
+        Bitboard(
+            self.0 >> n,
+            (self.1 >> n) | (self.0 << (128 - n)),
+            (self.2 >> n) | (self.1 << (128 - n)),
+            (self.3 >> n) | self.2 << (128 - n),
+        )
+    }
+}
+
+impl ShrAssign<u8> for Bitboard {
+    fn shr_assign(&mut self, n: u8) {
+        // 0 < n < 128. Use r_shift_save if n might be 0 or larger than 127
+        self.3 = (self.3 >> n) | self.2 << (128 - n);
+        self.2 = (self.2 >> n) | (self.1 << (128 - n));
+        self.1 = (self.1 >> n) | (self.0 << (128 - n));
+        self.0 >>= n;
+    }
+}
+
+impl PartialEq for Bitboard {
+    fn eq(&self, other: &Self) -> bool {
+        self.0 == other.0 && self.1 == other.1 && self.2 == other.2 && self.3 == other.3
+    }
+}
@@ -0,0 +1,229 @@
+use std::ops::{BitAnd, BitAndAssign, BitOr, BitOrAssign, BitXor, BitXorAssign, Not};
+use std::ops::{Shl, ShlAssign, Shr, ShrAssign};
+
+// Bitboard backend that stores the 512 bits in eight u64 lanes (lane i holds the bits 64 * i..64 * i + 63).
+// All operations are written as loops over the lanes without branches so that the compiler can turn
+// them into SIMD instructions. Build with RUSTFLAGS="-C target-cpu=native" to allow AVX2.
+//
+// The bits that move from one lane into its neighbor are shifted by 64 - n. Shifting by 64 overflows,
+// so it is done in two steps ((x >> 1) >> (63 - n)), which is 0 for n = 0.
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+#[repr(align(64))]
+pub struct Bitboard([u64; 8]);
+
+impl Bitboard {
+    pub const fn from_u128s(parts: [u128; 4]) -> Self {
+        // The parts are ordered from the most to the least significant bits, like in the fen
+        Bitboard([
+            parts[3] as u64,
+            (parts[3] >> 64) as u64,
+            parts[2] as u64,
+            (parts[2] >> 64) as u64,
+            parts[1] as u64,
+            (parts[1] >> 64) as u64,
+            parts[0] as u64,
+            (parts[0] >> 64) as u64,
+        ])
+    }
+
+    #[inline(always)]
+    pub fn to_u128s(&self) -> [u128; 4] {
+        let part = |lane: usize| (self.0[lane + 1] as u128) << 64 | self.0[lane] as u128;
+        [part(6), part(4), part(2), part(0)]
+    }
+
+    #[inline(always)]
+    pub fn empty() -> Self {
+        Bitboard([0; 8])
+    }
+
+    #[inline(always)]
+    pub(super) fn with_fields(destination: u16, fields: u128) -> Bitboard {
+        // Places the fields (relative to the top left corner) at the destination
+        let lane = (destination >> 6) as usize;
+        let n = (destination & 0b111111) as u32;
+        let (low, high) = (fields as u64, (fields >> 64) as u64);
+        let mut lanes = [0; 10];
+        lanes[lane] = low << n;
+        lanes[lane + 1] = high << n | (low >> 1) >> (63 - n);
+        lanes[lane + 2] = (high >> 1) >> (63 - n);
+        let mut board = [0; 8];
+        board.copy_from_slice(&lanes[..8]);
+        Bitboard(board)
+    }
+
+    pub fn bit(bit_idx: u16) -> Bitboard {
+        let mut board = Bitboard::empty();
+        board.0[bit_idx as usize >> 6] = 1 << (bit_idx & 0b111111);
+        board
+    }
+
+    pub fn check_bit(&self, bit_idx: u16) -> bool {
+        self.0[bit_idx as usize >> 6] & 1 << (bit_idx & 0b111111) != 0
+    }
+
+    pub fn flip_bit(&mut self, bit_idx: u16) {
+        self.0[bit_idx as usize >> 6] ^= 1 << (bit_idx & 0b111111);
+    }
+
+    pub fn trailing_zeros(&self) -> u16 {
+        for (lane, bits) in self.0.iter().enumerate() {
+            if *bits != 0 {
+                return bits.trailing_zeros() as u16 + lane as u16 * 64;
+            }
+        }
+        512
+    }
+
+    #[inline(always)]
+    pub fn for_each_bit<F: FnMut(u16)>(&self, mut f: F) {
+        // Calls f with the index of each set bit in the same order as the u128 backend:
+        // The bits 384..511 come first, then 256..383, 128..255 and 0..127 (ascending in each part).
+        for lane in [6, 7, 4, 5, 2, 3, 0, 1].iter() {
+            let mut bits = self.0[*lane];
+            while bits != 0 {
+                let bit = bits.trailing_zeros() as u16;
+                bits ^= 1 << bit;
+                f(bit + *lane as u16 * 64);
+            }
+        }
+    }
+
+    #[inline(always)]
+    pub fn count_ones(&self) -> u32 {
+        self.0.iter().map(|bits| bits.count_ones()).sum()
+    }
+
+    #[inline(always)]
+    pub fn is_empty(&self) -> bool {
+        self.0.iter().fold(0, |acc, bits| acc | bits) == 0
+    }
+
+    #[inline(always)]
+    pub fn not_empty(&self) -> bool {
+        !self.is_empty()
+    }
+}
+
+#[inline(always)]
+fn zip_lanes<F: Fn(u64, u64) -> u64>(mut board: Bitboard, other: Bitboard, f: F) -> Bitboard {
+    for (lane, other_lane) in board.0.iter_mut().zip(other.0.iter()) {
+        *lane = f(*lane, *other_lane);
+    }
+    board
+}
+
+impl BitXor for Bitboard {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitxor(self, other: Self) -> Self::Output {
+        zip_lanes(self, other, |a, b| a ^ b)
+    }
+}
+
+impl BitXorAssign for Bitboard {
+    #[inline(always)]
+    fn bitxor_assign(&mut self, other: Self) {
+        *self = zip_lanes(*self, other, |a, b| a ^ b);
+    }
+}
+
+impl BitAnd for Bitboard {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitand(self, other: Self) -> Self::Output {
+        zip_lanes(self, other, |a, b| a & b)
+    }
+}
+
+impl BitAndAssign for Bitboard {
+    #[inline(always)]
+    fn bitand_assign(&mut self, other: Self) {
+        *self = zip_lanes(*self, other, |a, b| a & b);
+    }
+}
+
+impl BitOr for Bitboard {
+    type Output = Self;
+
+    #[inline(always)]
+    fn bitor(self, other: Self) -> Self::Output {
+        zip_lanes(self, other, |a, b| a | b)
+    }
+}
+
+impl BitOrAssign for Bitboard {
+    #[inline(always)]
+    fn bitor_assign(&mut self, other: Self) {
+        *self = zip_lanes(*self, other, |a, b| a | b);
+    }
+}
+
+impl Not for Bitboard {
+    type Output = Self;
+
+    #[inline(always)]
+    fn not(mut self) -> Self::Output {
+        for lane in self.0.iter_mut() {
+            *lane = !*lane;
+        }
+        self
+    }
+}
+
+impl Shl<u8> for Bitboard {
+    type Output = Self;
+
+    #[inline(always)]
+    fn shl(self, n: u8) -> Self::Output {
+        // 0 < n < 128. Use l_shift_save if n might be 0 or larger than 127
+        let lanes = (n >> 6) as usize;
+        let n = (n & 0b111111) as u32;
+        let mut board = [0; 8];
+        for (lane, bits) in board[lanes..].iter_mut().zip(self.0.iter()) {
+            *lane = bits << n;
+        }
+        for (lane, bits) in board[lanes + 1..].iter_mut().zip(self.0.iter()) {
+            *lane |= (bits >> 1) >> (63 - n);
+        }
+        Bitboard(board)
+    }
+}
+
+impl ShlAssign<u8> for Bitboard {
+    #[inline(always)]
+    fn shl_assign(&mut self, n: u8) {
+        // 0 < n < 128. Use l_shift_save if n might be 0 or larger than 127
+        *self = *self << n;
+    }
+}
+
+impl Shr<u8> for Bitboard {
+    type Output = Self;
+
+    #[inline(always)]
+    fn shr(self, n: u8) -> Self::Output {
+        // 0 < n < 128. Use r_shift_save if n might be 0 or larger than 127
+        let lanes = (n >> 6) as usize;
+        let n = (n & 0b111111) as u32;
+        let mut board = [0; 8];
+        for (lane, bits) in board.iter_mut().zip(self.0[lanes..].iter()) {
+            *lane = bits >> n;
+        }
+        for (lane, bits) in board.iter_mut().zip(self.0[lanes + 1..].iter()) {
+            *lane |= (bits << 1) << (63 - n);
+        }
+        Bitboard(board)
+    }
+}
+
+impl ShrAssign<u8> for Bitboard {
+    #[inline(always)]
+    fn shr_assign(&mut self, n: u8) {
+        // 0 < n < 128. Use r_shift_save if n might be 0 or larger than 127
+        *self = *self >> n;
+    }
+}
@@ -0,0 +1,96 @@
+use super::{Bitboard, VALID_FIELDS};
+use std::fmt::{Display, Formatter, Result};
+use std::iter::FromIterator;
+
+// A field of the 20x20 board.
+// Bitboards store each row with an additional padding column, so the bit of the field (x, y) is
+// x + y * 21. Field only exists for the 400 fields of the board and converts between both.
+
+#[derive(Copy, Clone, PartialEq, Eq, Hash, Debug)]
+pub struct Field(u16); // Bit index of the field
+
+impl Field {
+    pub fn new(x: u16, y: u16) -> Option<Field> {
+        // Returns None if the coordinates are outside of the board
+        if x < 20 && y < 20 {
+            Some(Field(x + y * 21))
+        } else {
+            None
+        }
+    }
+
+    pub fn from_index(index: u16) -> Option<Field> {
+        // Returns None for bits in the padding column or below the board
+        if index < 420 && index % 21 != 20 {
+            Some(Field(index))
+        } else {
+            None
+        }
+    }
+
+    #[inline(always)]
+    pub fn index(self) -> u16 {
+        self.0
+    }
+
+    #[inline(always)]
+    pub fn x(self) -> u16 {
+        self.0 % 21
+    }
+
+    #[inline(always)]
+    pub fn y(self) -> u16 {
+        self.0 / 21
+    }
+}
+
+impl Display for Field {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        write!(f, "({}, {})", self.x(), self.y())
+    }
+}
+
+impl From<Field> for Bitboard {
+    fn from(field: Field) -> Bitboard {
+        Bitboard::bit(field.0)
+    }
+}
+
+pub struct FieldIter(Bitboard);
+
+impl FieldIter {
+    pub fn new(board: Bitboard) -> FieldIter {
+        // Bits outside of VALID_FIELDS are skipped
+        FieldIter(board & VALID_FIELDS)
+    }
+}
+
+impl Iterator for FieldIter {
+    type Item = Field;
+
+    fn next(&mut self) -> Option<Field> {
+        if self.0.is_empty() {
+            return None;
+        }
+        let index = self.0.trailing_zeros();
+        self.0.flip_bit(index);
+        Some(Field(index))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let count = self.0.count_ones() as usize;
+        (count, Some(count))
+    }
+}
+
+impl ExactSizeIterator for FieldIter {}
+
+impl FromIterator<Field> for Bitboard {
+    fn from_iter<I: IntoIterator<Item = Field>>(iter: I) -> Bitboard {
+        let mut board = Bitboard::empty();
+        for field in iter {
+            board |= Bitboard::from(field);
+        }
+        board
+    }
+}
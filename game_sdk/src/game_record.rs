@@ -0,0 +1,268 @@
+use super::{Action, GameState, IllegalActionReason, PieceType, Rules, StartPiecePolicy};
+use std::fmt::{Display, Formatter, Result};
+
+// A game record stores everything that is needed to replay a game.
+// It is serialized as a few "key: value" lines followed by one line per action:
+//     start: W
+//     rules: free (only if the start piece policy is not random)
+//     one: client_one
+//     two: client_two
+//     cause: REGULAR
+//     scores: 83 79
+//     actions:
+//     <serialized action> <time used in ms>
+//     ...
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum TerminationCause {
+    Regular,
+    Left,
+    RuleViolation,
+    SoftTimeout,
+    HardTimeout,
+    Unknown, // The game has not ended yet or the cause is not known
+}
+
+impl TerminationCause {
+    pub fn from_xml_name(name: &str) -> Option<Self> {
+        match name {
+            "REGULAR" => Some(TerminationCause::Regular),
+            "LEFT" => Some(TerminationCause::Left),
+            "RULE_VIOLATION" => Some(TerminationCause::RuleViolation),
+            "SOFT_TIMEOUT" => Some(TerminationCause::SoftTimeout),
+            "HARD_TIMEOUT" => Some(TerminationCause::HardTimeout),
+            "UNKNOWN" => Some(TerminationCause::Unknown),
+            _ => None,
+        }
+    }
+
+    pub fn to_xml_name(self) -> &'static str {
+        match self {
+            TerminationCause::Regular => "REGULAR",
+            TerminationCause::Left => "LEFT",
+            TerminationCause::RuleViolation => "RULE_VIOLATION",
+            TerminationCause::SoftTimeout => "SOFT_TIMEOUT",
+            TerminationCause::HardTimeout => "HARD_TIMEOUT",
+            TerminationCause::Unknown => "UNKNOWN",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum GameRecordError {
+    InvalidLine(usize, String), // Line number and content of a line that can't be parsed
+    MissingField(&'static str), // A required "key: value" line is missing
+    IllegalAction(u8, Action, IllegalActionReason), // Ply, action and the reason why it is illegal
+    ActionAfterGameOver(u8),    // The record contains actions after the end of the game
+    ScoreMismatch([i16; 2]),    // Scores of the replayed game if they don't match the record
+    TimeCountMismatch(usize, usize), // Number of actions and number of times if they differ
+    InvalidPlayerName(String),  // A player name that contains a line break
+}
+
+impl Display for GameRecordError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            GameRecordError::InvalidLine(line, content) => {
+                write!(f, "Invalid line {}: \"{}\"", line, content)
+            }
+            GameRecordError::MissingField(field) => write!(f, "Missing field \"{}\"", field),
+            GameRecordError::IllegalAction(ply, action, reason) => {
+                write!(f, "Illegal action in ply {}: {} ({})", ply, action, reason)
+            }
+            GameRecordError::ActionAfterGameOver(ply) => {
+                write!(f, "Action in ply {} after the game is over", ply)
+            }
+            GameRecordError::ScoreMismatch(scores) => write!(
+                f,
+                "The recorded scores don't match the replayed scores ({} {})",
+                scores[0], scores[1]
+            ),
+            GameRecordError::TimeCountMismatch(actions, times) => write!(
+                f,
+                "The record contains {} actions but {} times",
+                actions, times
+            ),
+            GameRecordError::InvalidPlayerName(name) => {
+                write!(f, "Invalid player name \"{}\"", name.escape_default())
+            }
+        }
+    }
+}
+
+impl std::error::Error for GameRecordError {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GameRecord {
+    pub start_piece_type: PieceType,
+    pub rules: Rules,
+    pub actions: Vec<Action>,      // All actions of the game including skips
+    pub times: Vec<u64>,           // Time used for each action in milliseconds (indexed by ply)
+    pub player_names: [String; 2], // Names of team one (Blue/Red) and team two (Yellow/Green)
+    pub scores: [i16; 2],          // Final scores of team one and team two
+    pub cause: TerminationCause,
+}
+
+impl GameRecord {
+    pub fn new(start_piece_type: PieceType, player_names: [String; 2]) -> Self {
+        Self {
+            start_piece_type,
+            rules: Rules::default(),
+            actions: Vec::new(),
+            times: Vec::new(),
+            player_names,
+            scores: [0, 0],
+            cause: TerminationCause::Unknown,
+        }
+    }
+
+    pub fn push(&mut self, action: Action, time: u64) {
+        self.actions.push(action);
+        self.times.push(time);
+    }
+
+    pub fn finish(&mut self, state: &GameState, cause: TerminationCause) {
+        self.scores = state.scores().teams;
+        self.cause = cause;
+    }
+
+    pub fn replay(&self) -> std::result::Result<GameState, GameRecordError> {
+        // Replays the game and checks the legality of each action
+        let mut state = GameState::new(self.rules, self.start_piece_type);
+        for action in self.actions.iter() {
+            if state.is_game_over() {
+                return Err(GameRecordError::ActionAfterGameOver(state.ply));
+            }
+            if let Err(reason) = state.check_action(action) {
+                return Err(GameRecordError::IllegalAction(state.ply, *action, reason));
+            }
+            state.do_action(*action);
+        }
+        let scores = state.scores().teams;
+        if self.cause == TerminationCause::Regular && scores != self.scores {
+            return Err(GameRecordError::ScoreMismatch(scores));
+        }
+        Ok(state)
+    }
+
+    pub fn serialize(&self) -> std::result::Result<String, GameRecordError> {
+        // Fails if a player name contains a line break or if not every action has a time
+        if self.actions.len() != self.times.len() {
+            return Err(GameRecordError::TimeCountMismatch(
+                self.actions.len(),
+                self.times.len(),
+            ));
+        }
+        if let Some(name) = self
+            .player_names
+            .iter()
+            .find(|name| name.contains(['\n', '\r']))
+        {
+            return Err(GameRecordError::InvalidPlayerName(name.clone()));
+        }
+        let mut string = format!("start: {}\n", self.start_piece_type.to_short_name());
+        if self.rules.start_piece_policy != StartPiecePolicy::Random {
+            string.push_str(&format!(
+                "rules: {}\n",
+                self.rules.start_piece_policy.kind_name()
+            ));
+        }
+        string.push_str(&format!(
+            "one: {}\ntwo: {}\ncause: {}\nscores: {} {}\nactions:\n",
+            self.player_names[0],
+            self.player_names[1],
+            self.cause.to_xml_name(),
+            self.scores[0],
+            self.scores[1],
+        ));
+        for (action, time) in self.actions.iter().zip(self.times.iter()) {
+            string.push_str(&format!("{} {}\n", action.serialize(), time));
+        }
+        Ok(string)
+    }
+
+    pub fn deserialize(string: &str) -> std::result::Result<Self, GameRecordError> {
+        let mut start_piece_type: Option<PieceType> = None;
+        let mut policy = StartPiecePolicy::Random;
+        let mut player_names: [Option<String>; 2] = [None, None];
+        let mut cause: Option<TerminationCause> = None;
+        let mut scores: Option<[i16; 2]> = None;
+        let mut actions: Option<(Vec<Action>, Vec<u64>)> = None;
+        for (line_number, line) in string.lines().enumerate() {
+            let line = line.trim();
+            let invalid_line = || GameRecordError::InvalidLine(line_number + 1, line.to_string());
+            if line.is_empty() {
+                continue;
+            }
+            if let Some((actions, times)) = actions.as_mut() {
+                let mut entries = line.split_whitespace();
+                let action = entries
+                    .next()
+                    .filter(|entry| entry.parse::<u16>().is_ok())
+                    .map(|entry| Action::deserialize(entry.to_string()))
+                    .ok_or_else(invalid_line)?;
+                if action.is_set() && (action.get_shape() > 90 || action.get_destination() > 418) {
+                    return Err(invalid_line());
+                }
+                let time = entries
+                    .next()
+                    .and_then(|entry| entry.parse::<u64>().ok())
+                    .ok_or_else(invalid_line)?;
+                if entries.next().is_some() {
+                    return Err(invalid_line());
+                }
+                actions.push(action);
+                times.push(time);
+                continue;
+            }
+            let (key, value) = line.split_once(':').ok_or_else(invalid_line)?;
+            let value = value.trim();
+            match key.trim() {
+                "start" => {
+                    start_piece_type =
+                        Some(PieceType::from_short_name(value).ok_or_else(invalid_line)?)
+                }
+                "rules" => {
+                    policy = StartPiecePolicy::from_kind_name(value, PieceType::Monomino)
+                        .ok_or_else(invalid_line)?
+                }
+                "one" => player_names[0] = Some(value.to_string()),
+                "two" => player_names[1] = Some(value.to_string()),
+                "cause" => {
+                    cause = Some(TerminationCause::from_xml_name(value).ok_or_else(invalid_line)?)
+                }
+                "scores" => {
+                    let entries: Vec<&str> = value.split_whitespace().collect();
+                    if entries.len() != 2 {
+                        return Err(invalid_line());
+                    }
+                    let one = entries[0].parse::<i16>().map_err(|_| invalid_line())?;
+                    let two = entries[1].parse::<i16>().map_err(|_| invalid_line())?;
+                    scores = Some([one, two]);
+                }
+                "actions" if value.is_empty() => actions = Some((Vec::new(), Vec::new())),
+                _ => return Err(invalid_line()),
+            }
+        }
+        let [one, two] = player_names;
+        let (actions, times) = actions.ok_or(GameRecordError::MissingField("actions"))?;
+        let start_piece_type = start_piece_type.ok_or(GameRecordError::MissingField("start"))?;
+        // The start line may come after the rules line
+        if let StartPiecePolicy::Fixed(_) = policy {
+            policy = StartPiecePolicy::Fixed(start_piece_type);
+        }
+        Ok(Self {
+            start_piece_type,
+            rules: Rules {
+                start_piece_policy: policy,
+            },
+            actions,
+            times,
+            player_names: [
+                one.ok_or(GameRecordError::MissingField("one"))?,
+                two.ok_or(GameRecordError::MissingField("two"))?,
+            ],
+            scores: scores.ok_or(GameRecordError::MissingField("scores"))?,
+            cause: cause.ok_or(GameRecordError::MissingField("cause"))?,
+        })
+    }
+}
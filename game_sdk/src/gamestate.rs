@@ -1,30 +1,251 @@
-use super::hashing::{DESTINATION_HASH, PLY_HASH, SHAPE_HASH};
-use super::{Action, ActionList, Bitboard, PieceType};
+use super::bitboard::PIECE_SHAPES;
+use super::hashing::{
+    DESTINATION_HASH, FREE_START_PIECE_HASH, MONOMINO_PLACED_LAST_HASH, PLY_HASH, SHAPE_HASH,
+    SKIPPED_HASH, START_PIECE_TYPE_HASH,
+};
+use super::{analysis, Action, ActionList, Bitboard, BoardViolation, Field, PieceType};
+use super::{Rules, StartPiecePolicy};
 use super::{PIECE_TYPES, START_FIELDS, VALID_FIELDS};
+use rand::{rngs::SmallRng, SeedableRng};
 use std::fmt::{Display, Formatter, Result};
 
-#[derive(Clone, Eq, PartialEq)]
+// Letters that are used for the colors in the board notation (indexed by color)
+pub const COLOR_LETTERS: [char; 4] = ['B', 'Y', 'R', 'G'];
+
+// Number of space separated entries in a fen: data, pieces and four u128 for each of the four boards
+const FEN_FIELDS: usize = 18;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum FenError {
+    MissingField(usize),             // The fen ended before the entry with this index
+    TooManyFields(usize),            // Number of entries found
+    InvalidInteger(usize, String),   // Index and content of the entry that is not a valid integer
+    InvalidStartPieceType(usize),    // Index of the start piece type
+    InvalidPly(u8),                  // The ply is larger than the maximum ply of a game
+    OverlappingColors(usize, usize), // Two colors occupy the same field
+    FieldOutsideBoard(usize),        // A color occupies a field that is not in VALID_FIELDS
+    InconsistentPieces(usize),       // The board of the color does not match its pieces_left
+    InconsistentPly,                 // The number of placed pieces and skips does not match the ply
+    InconsistentSkipMask,            // The skip history is not valid
+    InvalidLine(usize, String), // Line number and content of an invalid line of the board notation
+    InvalidRowCount(u16),       // The board notation does not contain exactly 20 rows
+    IllegalBoard(Box<BoardViolation>), // The first rule of the game that the boards violate
+    InvalidRules(u8),           // The code of the start piece policy is unknown
+    InvalidDataBits,            // The data entry has bits set above the start piece policy
+}
+
+impl Display for FenError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            FenError::MissingField(index) => write!(
+                f,
+                "Missing entry {} (expected {} entries)",
+                index, FEN_FIELDS
+            ),
+            FenError::TooManyFields(n) => {
+                write!(f, "Too many entries (expected {}, found {})", FEN_FIELDS, n)
+            }
+            FenError::InvalidInteger(index, entry) => {
+                write!(f, "Entry {} is not a valid integer: \"{}\"", index, entry)
+            }
+            FenError::InvalidStartPieceType(index) => {
+                write!(f, "Invalid start piece type {}", index)
+            }
+            FenError::InvalidPly(ply) => write!(f, "Invalid ply {}", ply),
+            FenError::OverlappingColors(a, b) => {
+                write!(f, "Colors {} and {} occupy the same fields", a, b)
+            }
+            FenError::FieldOutsideBoard(color) => {
+                write!(f, "Color {} occupies fields outside of the board", color)
+            }
+            FenError::InconsistentPieces(color) => write!(
+                f,
+                "The board of color {} does not match its pieces left",
+                color
+            ),
+            FenError::InconsistentPly => write!(
+                f,
+                "The number of placed pieces and skips does not match the ply"
+            ),
+            FenError::InconsistentSkipMask => write!(f, "Invalid skip mask"),
+            FenError::InvalidLine(line, content) => {
+                write!(f, "Invalid line {}: \"{}\"", line, content)
+            }
+            FenError::InvalidRowCount(rows) => write!(f, "Expected 20 rows, found {}", rows),
+            FenError::IllegalBoard(violation) => write!(f, "Illegal board: {}", violation),
+            FenError::InvalidRules(code) => write!(f, "Invalid start piece policy {}", code),
+            FenError::InvalidDataBits => write!(f, "Unknown bits set in the data entry"),
+        }
+    }
+}
+
+impl std::error::Error for FenError {}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum IllegalActionReason {
+    InvalidShape(u16),             // The shape index is not one of the 91 shapes
+    WrongStartPiece(PieceType),    // The first piece of each color has to be the start piece type
+    PieceAlreadyPlaced(PieceType), // Each piece type can only be placed once per color
+    NoCornerContact, // The piece does not touch a corner of its own color (or a start field)
+    EdgeContactWithOwnColor, // The piece shares an edge with a piece of its own color
+    Overlap,         // The piece covers fields that are already occupied
+    OutsideBoard,    // The piece is (partially) outside of the board
+    SkipWithPossibleActions, // The color skipped even though it could place a piece
+}
+
+impl Display for IllegalActionReason {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            IllegalActionReason::InvalidShape(shape) => write!(f, "Invalid shape {}", shape),
+            IllegalActionReason::WrongStartPiece(start_piece_type) => {
+                write!(
+                    f,
+                    "Invalid piece type. Start piece type is {}",
+                    start_piece_type
+                )
+            }
+            IllegalActionReason::PieceAlreadyPlaced(piece_type) => {
+                write!(f, "{} has already been placed", piece_type)
+            }
+            IllegalActionReason::NoCornerContact => write!(f, "Piece does not touch a corner"),
+            IllegalActionReason::EdgeContactWithOwnColor => {
+                write!(f, "Piece touches an edge of its own color")
+            }
+            IllegalActionReason::Overlap => write!(f, "Piece overlaps occupied fields"),
+            IllegalActionReason::OutsideBoard => write!(f, "Piece is outside of the board"),
+            IllegalActionReason::SkipWithPossibleActions => {
+                write!(f, "Can't skip while there are possible actions")
+            }
+        }
+    }
+}
+
+impl std::error::Error for IllegalActionReason {}
+
+#[derive(Debug, Clone, Eq)]
 pub struct GameState {
     pub ply: u8,                         // Current turn of the GameState
     pub board: [Bitboard; 4],            // 512-bit bitboards (indexed by color)
     pub pieces_left: [[bool; 4]; 21], // Array that stores which player has which pieces left (indexed by piece_type, color)
-    pub monomino_placed_last: [bool; 4], // Saves whether a player's last action was a Monomino (indexed by color)
+    pub monomino_placed_last: [bool; 4], // Saves whether a player's last placed piece was the Monomino (indexed by color)
     pub skipped: u64,                    // Keeps track of which player skipped
     pub start_piece_type: PieceType, // The piece type that each player has to place in the first round
-    pub hash: u64,                   // Hash of the current state. Only used in Minimax
+    pub rules: Rules,                // Rule variation of the game (see Rules)
+    pub hash: u64,                   // Zobrist hash of the current state (see compute_hash)
+    // The piece that cleared monomino_placed_last, so that undo_action can restore it (indexed by color)
+    pub(crate) piece_after_monomino: [Option<PieceType>; 4],
+}
+
+impl PartialEq for GameState {
+    fn eq(&self, other: &Self) -> bool {
+        // piece_after_monomino is only needed to undo actions and doesn't change the position
+        self.ply == other.ply
+            && self.board == other.board
+            && self.pieces_left == other.pieces_left
+            && self.monomino_placed_last == other.monomino_placed_last
+            && self.skipped == other.skipped
+            && self.start_piece_type == other.start_piece_type
+            && self.rules == other.rules
+            && self.hash == other.hash
+    }
 }
 
 impl GameState {
     pub fn random() -> GameState {
         // Returns an empty GameState with a random start_piece_type
-        GameState {
+        GameState::with_start_piece_type(PieceType::random_pentomino())
+    }
+
+    pub fn random_with_seed(seed: u64) -> GameState {
+        // Returns an empty GameState with a start_piece_type that only depends on the seed
+        let mut rng = SmallRng::seed_from_u64(seed);
+        GameState::with_start_piece_type(PieceType::random_pentomino_with_rng(&mut rng))
+    }
+
+    pub fn with_start_piece_type(start_piece_type: PieceType) -> GameState {
+        // Returns an empty GameState with the rules of the Software-Challenge
+        GameState::new(Rules::default(), start_piece_type)
+    }
+
+    pub fn with_rules(rules: Rules) -> GameState {
+        // Returns an empty GameState. The start piece type is random if the rules require one.
+        GameState::new(rules, PieceType::random_pentomino())
+    }
+
+    pub fn with_rules_and_seed(rules: Rules, seed: u64) -> GameState {
+        let mut rng = SmallRng::seed_from_u64(seed);
+        GameState::new(rules, PieceType::random_pentomino_with_rng(&mut rng))
+    }
+
+    pub fn new(rules: Rules, start_piece_type: PieceType) -> GameState {
+        // The start piece type of StartPiecePolicy::Fixed overrides start_piece_type.
+        // It is ignored with StartPiecePolicy::FreeChoice.
+        let start_piece_type = match rules.start_piece_policy {
+            StartPiecePolicy::Fixed(piece_type) => piece_type,
+            _ => start_piece_type,
+        };
+        let mut state = GameState {
             ply: 0,
             board: [Bitboard::empty(); 4],
             pieces_left: [[true; 4]; 21],
             monomino_placed_last: [false; 4],
             skipped: 0,
-            start_piece_type: PieceType::random_pentomino(),
+            start_piece_type,
+            rules,
             hash: 0,
+            piece_after_monomino: [None; 4],
+        };
+        state.hash = state.compute_hash();
+        state
+    }
+
+    pub fn compute_hash(&self) -> u64 {
+        // Computes the hash of the state from scratch.
+        // do_action and undo_action update the hash incrementally.
+        let start_piece_hash = if self.rules.requires_start_piece() {
+            START_PIECE_TYPE_HASH[self.start_piece_type as usize]
+        } else {
+            FREE_START_PIECE_HASH
+        };
+        let mut hash = start_piece_hash ^ SKIPPED_HASH[(self.skipped & 0b1111) as usize];
+        for ply_hash in PLY_HASH.iter().take(self.ply as usize) {
+            hash ^= ply_hash;
+        }
+        for color in 0..4 {
+            for piece in self.board[color].get_pieces().iter() {
+                if piece.is_set() {
+                    let destination = piece.get_destination() as usize;
+                    let shape = piece.get_shape() as usize;
+                    hash ^= SHAPE_HASH[shape][color] ^ DESTINATION_HASH[destination][color];
+                }
+            }
+            if self.monomino_placed_last[color] {
+                hash ^= MONOMINO_PLACED_LAST_HASH[color];
+            }
+        }
+        hash
+    }
+
+    #[inline(always)]
+    pub fn legal_fields(&self, color: usize) -> Bitboard {
+        // Fields that can be covered by a new piece of the color.
+        // Computed on demand: keeping it up to date in do_action/undo_action halved the perft speed.
+        !(self.get_occupied_fields() | self.board[color].neighbors()) & VALID_FIELDS
+    }
+
+    #[inline(always)]
+    pub fn placement_fields(&self, color: usize) -> Bitboard {
+        // Legal fields at which a new piece of the color touches a corner of its own pieces
+        self.placement_corners(color) & self.legal_fields(color)
+    }
+
+    #[inline(always)]
+    pub fn placement_corners(&self, color: usize) -> Bitboard {
+        // In the first round each color has to cover a start field, after that a corner of its own pieces
+        if self.is_first_round(color) {
+            START_FIELDS
+        } else {
+            self.board[color].diagonal_neighbors()
         }
     }
 
@@ -60,11 +281,14 @@ impl GameState {
     }
 
     pub fn do_action(&mut self, action: Action) {
-        debug_assert!(self.validate_action(&action));
+        // Skips are not checked because playouts may skip even if there are possible actions
+        debug_assert!(action.is_skip() || self.validate_action(&action));
         self.hash ^= PLY_HASH[self.ply as usize];
         let color = self.get_current_color();
         if action.is_skip() {
+            self.hash ^= SKIPPED_HASH[(self.skipped & 0b1111) as usize];
             self.skipped = self.skipped << 4 | self.skipped & 0b1111 | 1 << color;
+            self.hash ^= SKIPPED_HASH[(self.skipped & 0b1111) as usize];
         } else {
             let destination = action.get_destination();
             let shape = action.get_shape() as usize;
@@ -72,10 +296,16 @@ impl GameState {
             self.pieces_left[piece_type as usize][color] = false;
             self.board[color] ^= Bitboard::with_piece(destination, shape);
             self.hash ^= SHAPE_HASH[shape][color] ^ DESTINATION_HASH[destination as usize][color];
-            self.monomino_placed_last[color] = piece_type == PieceType::Monomino;
+            if piece_type == PieceType::Monomino {
+                self.set_monomino_placed_last(color, true);
+            } else if self.monomino_placed_last[color] {
+                self.set_monomino_placed_last(color, false);
+                self.piece_after_monomino[color] = Some(piece_type);
+            }
         };
         self.ply += 1;
         debug_assert!(self.check_integrity());
+        debug_assert_eq!(self.hash, self.compute_hash());
     }
 
     pub fn undo_action(&mut self, action: Action) {
@@ -83,118 +313,262 @@ impl GameState {
         self.hash ^= PLY_HASH[self.ply as usize];
         let color = self.get_current_color();
         if action.is_skip() {
+            self.hash ^= SKIPPED_HASH[(self.skipped & 0b1111) as usize];
             self.skipped >>= 4;
+            self.hash ^= SKIPPED_HASH[(self.skipped & 0b1111) as usize];
         } else {
             let destination = action.get_destination();
             let shape = action.get_shape() as usize;
             let piece_type = PieceType::from_shape(shape);
+            if piece_type == PieceType::Monomino {
+                self.set_monomino_placed_last(color, false);
+            } else if self.piece_after_monomino[color] == Some(piece_type) {
+                self.set_monomino_placed_last(color, true);
+                self.piece_after_monomino[color] = None;
+            }
             self.pieces_left[piece_type as usize][color] = true;
             self.board[color] ^= Bitboard::with_piece(destination, shape);
             self.hash ^= SHAPE_HASH[shape][color] ^ DESTINATION_HASH[destination as usize][color];
         }
         debug_assert!(self.check_integrity());
+        debug_assert_eq!(self.hash, self.compute_hash());
+    }
+
+    #[inline(always)]
+    fn set_monomino_placed_last(&mut self, color: usize, placed_last: bool) {
+        if self.monomino_placed_last[color] != placed_last {
+            self.monomino_placed_last[color] = placed_last;
+            self.hash ^= MONOMINO_PLACED_LAST_HASH[color];
+        }
     }
 
     pub fn validate_action(&self, action: &Action) -> bool {
+        self.check_action(action).is_ok()
+    }
+
+    pub fn check_action(&self, action: &Action) -> std::result::Result<(), IllegalActionReason> {
+        // Returns the first rule that the action violates
         let color = self.get_current_color();
         if action.is_skip() {
-            return true;
+            if !self.has_color_skipped(color) {
+                let mut al = ActionList::default();
+                self.get_possible_actions(&mut al);
+                if !al[0].is_skip() {
+                    return Err(IllegalActionReason::SkipWithPossibleActions);
+                }
+            }
+            return Ok(());
         }
-        let mut is_valid = true;
         let destination = action.get_destination();
         let shape = action.get_shape() as usize;
+        if shape >= PIECE_SHAPES.len() {
+            return Err(IllegalActionReason::InvalidShape(shape as u16));
+        }
         let piece_type = PieceType::from_shape(shape);
-        if self.ply < 4 && piece_type != self.start_piece_type {
-            println!(
-                "Invalid piece type. Start piece type is {}",
-                self.start_piece_type
-            );
-            is_valid = false;
+        if let Some(start_piece_type) = self.required_piece_type(color) {
+            if piece_type != start_piece_type {
+                return Err(IllegalActionReason::WrongStartPiece(start_piece_type));
+            }
         }
         if !self.pieces_left[piece_type as usize][color] {
-            println!("Can't place piece that has already been placed.");
-            return false;
+            return Err(IllegalActionReason::PieceAlreadyPlaced(piece_type));
         }
         let piece = Bitboard::with_piece(destination, shape);
-        let own_fields = self.board[color];
-        let other_fields = self.get_occupied_fields() & !own_fields;
-        let legal_fields = !(own_fields | other_fields | own_fields.neighbors()) & VALID_FIELDS;
-        let p = if self.ply > 3 {
-            own_fields.diagonal_neighbors() & legal_fields
-        } else {
-            START_FIELDS & !other_fields
-        };
-        if (piece & p).is_empty() {
-            println!("Piece does not touch a corner");
-            is_valid = false;
+        // Parts of the piece are shifted out of the bitboard or wrap around into the next row
+        if piece_type.piece_size() != piece.count_ones() as u8 || piece & VALID_FIELDS != piece {
+            return Err(IllegalActionReason::OutsideBoard);
         }
-        if piece & legal_fields != piece {
-            println!("Piece destination is not valid");
-            is_valid = false;
+        if (piece & self.get_occupied_fields()).not_empty() {
+            return Err(IllegalActionReason::Overlap);
         }
-        if piece_type.piece_size() != piece.count_ones() as u8 {
-            println!("Piece shifted to invalid position");
-            is_valid = false;
+        if (piece & self.board[color].neighbors()).not_empty() {
+            return Err(IllegalActionReason::EdgeContactWithOwnColor);
         }
-        if !is_valid {
-            println!("{}", action.to_string());
-            println!("{}", piece.to_string());
-            println!("{}", action.visualize());
-            println!("{}", self);
+        if (piece & self.placement_corners(color)).is_empty() {
+            return Err(IllegalActionReason::NoCornerContact);
         }
-        is_valid
+        Ok(())
     }
 
     pub fn check_integrity(&self) -> bool {
-        for color in 0..4 {
-            let pieces = self.board[color].get_pieces();
-            let mut pieces_left: [bool; 21] = [true; 21];
-            for piece in pieces.iter() {
-                let piece_type = PieceType::from_shape(piece.get_shape() as usize);
-                pieces_left[piece_type as usize] = false;
-            }
-            for piece_type in PIECE_TYPES.iter() {
-                if self.pieces_left[*piece_type as usize][color]
-                    != pieces_left[*piece_type as usize]
-                {
-                    return false;
-                }
+        (0..4).all(|color| self.check_color_integrity(color))
+    }
+
+    pub fn check_color_integrity(&self, color: usize) -> bool {
+        let pieces = self.board[color].get_pieces();
+        let mut pieces_left: [bool; 21] = [true; 21];
+        for piece in pieces.iter() {
+            if piece.is_skip() {
+                // The board contains fields that don't form a valid piece
+                return false;
             }
+            let piece_type = PieceType::from_shape(piece.get_shape() as usize);
+            pieces_left[piece_type as usize] = false;
         }
-        for color in 0..4 {
-            let mut should_have: u32 = 0;
-            for piece_type in PIECE_TYPES.iter() {
-                if !self.pieces_left[*piece_type as usize][color] {
-                    should_have += piece_type.piece_size() as u32;
-                }
-            }
-            if should_have != self.board[color].count_ones() {
+        for piece_type in PIECE_TYPES.iter() {
+            if self.pieces_left[*piece_type as usize][color] != pieces_left[*piece_type as usize] {
                 return false;
             }
         }
-        true
+        let mut should_have: u32 = 0;
+        for piece_type in PIECE_TYPES.iter() {
+            if !self.pieces_left[*piece_type as usize][color] {
+                should_have += piece_type.piece_size() as u32;
+            }
+        }
+        should_have == self.board[color].count_ones()
     }
 
     pub fn get_possible_actions(&self, al: &mut ActionList) {
-        let color = self.get_current_color();
+        self.get_possible_actions_for_color(self.get_current_color(), al);
+    }
+
+    pub fn get_possible_actions_for_color(&self, color: usize, al: &mut ActionList) {
+        // Returns the actions that the color could play if it was its turn in the current state
         al.clear();
         if self.has_color_skipped(color) {
             // The color has no possible actions if it had to skip in a previous round
             al.push(Action::SKIP);
             return;
         }
-        // Fields that are occupied by the current color
-        let own_fields = self.board[color];
-        // All fields that are occupied by the other colors
-        let other_fields = self.get_occupied_fields() & !own_fields;
-        // Fields that newly placed pieces can occupy
-        let legal_fields = !(own_fields | other_fields | own_fields.neighbors()) & VALID_FIELDS;
-        // Calculate the corners of existing pieces at which new pieces can be placed
-        let p = if self.ply > 3 {
-            own_fields.diagonal_neighbors() & legal_fields
+        self.generate_actions(color, self.placement_corners(color), al);
+        if al.size == 0 {
+            al.push(Action::SKIP);
+        }
+    }
+
+    pub fn get_possible_actions_for_piece(
+        &self,
+        piece_type: PieceType,
+        color: usize,
+        al: &mut ActionList,
+    ) {
+        // Returns the actions of get_possible_actions_for_color that place a piece of the piece type.
+        // Unlike get_possible_actions_for_color, the list is left empty instead of containing a skip.
+        al.clear();
+        if self.has_color_skipped(color)
+            || self
+                .required_piece_type(color)
+                .is_some_and(|required| required != piece_type)
+        {
+            return;
+        }
+        let shortcuts = self.get_shortcuts(color, self.placement_corners(color));
+        self.generate_piece_actions(color, piece_type, shortcuts, al);
+    }
+
+    pub fn get_possible_actions_at_corner(&self, field: Field, color: usize, al: &mut ActionList) {
+        // Returns the actions of get_possible_actions_for_color that cover the field.
+        // The list is empty if the field is not a placement field of the color.
+        al.clear();
+        if self.has_color_skipped(color) {
+            return;
+        }
+        let corner = self.placement_corners(color) & Bitboard::from(field);
+        if corner.not_empty() {
+            self.generate_actions(color, corner, al);
+        }
+    }
+
+    pub fn count_possible_actions(&self, color: usize) -> usize {
+        // Returns the number of actions of get_possible_actions_for_color without creating them.
+        // Returns 0 if the color can only skip.
+        if self.has_color_skipped(color) {
+            return 0;
+        }
+        let mut counter = ActionCounter(0);
+        self.generate_actions(color, self.placement_corners(color), &mut counter);
+        counter.0
+    }
+
+    pub fn is_color_dead(&self, color: usize) -> bool {
+        // Returns true if the color will never be able to place another piece.
+        // Pieces only ever get added to the board, so a color that can't place a piece now stays blocked.
+        // This is a cheap test that only compares the fields around the placement fields with the size of
+        // the smallest remaining piece. It is exact while the color has the monomino left. Without it,
+        // a color that can't place any piece might not be detected.
+        if self.is_first_round(color) {
+            return self.has_color_skipped(color);
+        }
+        let corners = analysis::placement_fields(self, color);
+        if self.pieces_left[PieceType::Monomino as usize][color] {
+            return corners.is_empty();
+        }
+        let legal_fields = self.legal_fields(color);
+        if self.pieces_left[PieceType::Domino as usize][color] {
+            // A second field next to the placement field
+            return (corners & legal_fields.neighbors()).is_empty();
+        }
+        // A third field: Either the placement field or one of its neighbors has two legal neighbors
+        let mut one_neighbor = Bitboard::empty();
+        let mut two_neighbors = Bitboard::empty();
+        for shift in [1, 21].iter() {
+            for neighbors in [legal_fields << *shift, legal_fields >> *shift].iter() {
+                two_neighbors |= one_neighbor & *neighbors;
+                one_neighbor |= *neighbors;
+            }
+        }
+        (corners & (two_neighbors | (two_neighbors & legal_fields).neighbors())).is_empty()
+    }
+
+    #[inline(always)]
+    pub(crate) fn is_first_round(&self, color: usize) -> bool {
+        // Whether the next turn of the color is in the first round
+        self.ply as usize + ((color + 4 - self.get_current_color()) & 0b11) < 4
+    }
+
+    #[inline(always)]
+    pub fn required_piece_type(&self, color: usize) -> Option<PieceType> {
+        // The piece type that the color has to place in its next turn, if the rules require one
+        if self.rules.requires_start_piece() && self.is_first_round(color) {
+            Some(self.start_piece_type)
         } else {
-            START_FIELDS & !other_fields
-        };
+            None
+        }
+    }
+
+    fn generate_actions<T: ActionSink>(&self, color: usize, corners: Bitboard, al: &mut T) {
+        // Generates the actions that cover one of the corners
+        let shortcuts = self.get_shortcuts(color, corners);
+        if let Some(start_piece_type) = self.required_piece_type(color) {
+            // Only the start piece type can be placed in the first round
+            self.generate_piece_actions(color, start_piece_type, shortcuts, al);
+            return;
+        }
+        // Add all legal actions for each piece type
+        let generators = action_generators::<T>();
+        for (piece_type, generator) in generators.iter().enumerate() {
+            if self.pieces_left[piece_type + 1][color] {
+                generator(shortcuts, al);
+            }
+        }
+        if self.pieces_left[PieceType::Monomino as usize][color] {
+            al.append(shortcuts[12], 0);
+        }
+    }
+
+    #[inline(always)]
+    fn generate_piece_actions<T: ActionSink>(
+        &self,
+        color: usize,
+        piece_type: PieceType,
+        shortcuts: [Bitboard; 13],
+        al: &mut T,
+    ) {
+        if !self.pieces_left[piece_type as usize][color] {
+            return;
+        }
+        if piece_type == PieceType::Monomino {
+            al.append(shortcuts[12], 0);
+        } else {
+            action_generators::<T>()[piece_type as usize - 1](shortcuts, al);
+        }
+    }
+
+    #[inline(always)]
+    fn get_shortcuts(&self, color: usize, corners: Bitboard) -> [Bitboard; 13] {
+        // Fields that newly placed pieces can occupy
+        let legal_fields = self.legal_fields(color);
         // Create a lot of shortcuts to speed up the action generation
         let mut shortcuts: [Bitboard; 13] = [Bitboard::empty(); 13];
         shortcuts[0] = legal_fields & (legal_fields >> 1 & VALID_FIELDS);
@@ -209,33 +583,9 @@ impl GameState {
         shortcuts[9] = legal_fields;
         shortcuts[10] = shortcuts[6] & (legal_fields >> 63 & VALID_FIELDS);
         shortcuts[11] = shortcuts[0] & shortcuts[0] >> 21;
-        shortcuts[12] = p;
-
-        // Add all legal actions for each piece type to the ActionList
-        for (piece_type, generator) in ACTION_GENERATORS.iter().enumerate() {
-            if self.pieces_left[piece_type + 1][color] {
-                generator(shortcuts, al);
-            }
-        }
-        if self.pieces_left[PieceType::Monomino as usize][color] {
-            al.append(p, 0);
-        }
-        if self.ply < 4 {
-            // Remove all piece types that are not the start piece type
-            let mut idx = 0;
-            for i in 0..al.size {
-                let shape = al[i].get_shape() as usize;
-                let piece_type = PieceType::from_shape(shape);
-                if piece_type == self.start_piece_type {
-                    al.swap(idx, i);
-                    idx += 1;
-                }
-            }
-            al.size = idx;
-        }
-        if al.size == 0 {
-            al.push(Action::SKIP);
-        }
+        // The corners at which new pieces can be placed
+        shortcuts[12] = corners & legal_fields;
+        shortcuts
     }
 
     #[inline(always)]
@@ -246,20 +596,14 @@ impl GameState {
     pub fn game_result(&self) -> i16 {
         // Only works when the game is over
         // Returns a positive value if team Blue/Red won, a negative value if team Yellow/Green won, and 0 if the game ended in a draw
-        let mut result: i16 = 0;
-        for (color, board) in self.board.iter().enumerate() {
-            let fields = board.count_ones() as i16;
-            result -= (fields
-                + (fields == 89) as i16 * (15 + 5 * (self.monomino_placed_last[color]) as i16))
-                * (((color as i16 & 0b1) << 1) - 1);
-        }
-        result
+        self.scores().difference()
     }
 
     pub fn to_fen(&self) -> String {
         let mut data = (self.start_piece_type as u128) << 4;
         data |= (self.ply as u128) << 9;
         data |= (self.skipped as u128) << 17;
+        data |= (self.rules.start_piece_policy.to_code() as u128) << 81;
         let mut pieces: u128 = 0;
         for color in 0..4 {
             for piece_type in 0..21 {
@@ -281,13 +625,43 @@ impl GameState {
     }
 
     pub fn from_fen(string: String) -> GameState {
-        let mut entries: Vec<&str> = string.split(' ').collect();
+        match GameState::try_from_fen(&string) {
+            Ok(state) => state,
+            Err(error) => panic!("Invalid fen \"{}\": {}", string, error),
+        }
+    }
+
+    pub fn try_from_fen(string: &str) -> std::result::Result<GameState, FenError> {
+        let entries: Vec<&str> = string.split_whitespace().collect();
+        if entries.len() > FEN_FIELDS {
+            return Err(FenError::TooManyFields(entries.len()));
+        }
+        let parse = |index: usize| -> std::result::Result<u128, FenError> {
+            let entry = entries.get(index).ok_or(FenError::MissingField(index))?;
+            entry
+                .parse::<u128>()
+                .map_err(|_| FenError::InvalidInteger(index, entry.to_string()))
+        };
         let mut state = GameState::default();
-        let data = entries.remove(0).parse::<u128>().unwrap();
-        state.start_piece_type = PIECE_TYPES[(data >> 4 & 0b11111) as usize];
+        let data = parse(0)?;
+        let start_piece_type = (data >> 4 & 0b11111) as usize;
+        if start_piece_type >= PIECE_TYPES.len() {
+            return Err(FenError::InvalidStartPieceType(start_piece_type));
+        }
+        state.start_piece_type = PIECE_TYPES[start_piece_type];
         state.ply = (data >> 9 & 0b11111111) as u8;
+        if state.ply as usize >= PLY_HASH.len() {
+            return Err(FenError::InvalidPly(state.ply));
+        }
+        if data >> 83 != 0 {
+            return Err(FenError::InvalidDataBits);
+        }
+        let policy = (data >> 81 & 0b11) as u8;
+        state.rules.start_piece_policy =
+            StartPiecePolicy::from_code(policy, state.start_piece_type)
+                .ok_or(FenError::InvalidRules(policy))?;
         state.skipped = (data >> 17) as u64;
-        let pieces = entries.remove(0).parse::<u128>().unwrap();
+        let pieces = parse(1)?;
         for color in 0..4 {
             for piece_type in 0..21 {
                 if pieces & 1 << (piece_type + color * 21) != 0 {
@@ -297,12 +671,233 @@ impl GameState {
             state.monomino_placed_last[color] = data & (1 << color) != 0;
         }
         for color in 0..4 {
-            state.board[color].0 = entries.remove(0).parse::<u128>().unwrap();
-            state.board[color].1 = entries.remove(0).parse::<u128>().unwrap();
-            state.board[color].2 = entries.remove(0).parse::<u128>().unwrap();
-            state.board[color].3 = entries.remove(0).parse::<u128>().unwrap();
+            state.board[color] = Bitboard::from_u128s([
+                parse(2 + color * 4)?,
+                parse(3 + color * 4)?,
+                parse(4 + color * 4)?,
+                parse(5 + color * 4)?,
+            ]);
         }
-        state
+        state.finish_loading()
+    }
+
+    pub(crate) fn finish_loading(mut self) -> std::result::Result<GameState, FenError> {
+        // Validates a state that was read from the fen or another format and computes the hash
+        if self.ply as usize >= PLY_HASH.len() {
+            return Err(FenError::InvalidPly(self.ply));
+        }
+        self.validate_fen_state()?;
+        self.hash = self.compute_hash();
+        Ok(self)
+    }
+
+    fn validate_fen_state(&self) -> std::result::Result<(), FenError> {
+        for color in 0..4 {
+            if (self.board[color] & !VALID_FIELDS).not_empty() {
+                return Err(FenError::FieldOutsideBoard(color));
+            }
+            for other_color in color + 1..4 {
+                if (self.board[color] & self.board[other_color]).not_empty() {
+                    return Err(FenError::OverlappingColors(color, other_color));
+                }
+            }
+        }
+        for color in 0..4 {
+            let monomino_left = self.pieces_left[PieceType::Monomino as usize][color];
+            if !self.check_color_integrity(color)
+                || (self.monomino_placed_last[color] && monomino_left)
+            {
+                return Err(FenError::InconsistentPieces(color));
+            }
+        }
+        // Boards that match pieces_left can still be impossible to reach in a game
+        if let Some(violation) = self.validate_board().into_iter().next() {
+            return Err(FenError::IllegalBoard(Box::new(violation)));
+        }
+        // Each nibble of the skip history has to contain all colors that skipped before
+        let mut skipped = self.skipped;
+        let mut skips: usize = 0;
+        while skipped != 0 {
+            let current = skipped & 0b1111;
+            let previous = skipped >> 4 & 0b1111;
+            if current & previous != previous || current == 0 {
+                return Err(FenError::InconsistentSkipMask);
+            }
+            skipped >>= 4;
+            skips += 1;
+        }
+        // Every ply is either a placed piece or a skip
+        let mut placed: usize = 0;
+        for color in 0..4 {
+            let placed_by_color = (0..21)
+                .filter(|piece_type| !self.pieces_left[*piece_type][color])
+                .count();
+            let turns = (self.ply as usize + 3 - color) / 4;
+            if placed_by_color > turns {
+                return Err(FenError::InconsistentPly);
+            }
+            placed += placed_by_color;
+        }
+        // The skip history only stores the last 16 skips
+        if placed + skips > self.ply as usize || (skips < 16 && placed + skips != self.ply as usize)
+        {
+            return Err(FenError::InconsistentPly);
+        }
+        Ok(())
+    }
+
+    pub fn to_board_notation(&self) -> String {
+        // Human-readable alternative to the fen
+        //     ply: 8
+        //     start: W
+        //     skipped: -
+        //     monomino_last: -
+        //     rules: free (only if the start piece policy is not random)
+        // followed by 20 rows with 20 fields each (B, Y, R, G or .)
+        let colors_to_string = |mask: u64| -> String {
+            if mask == 0 {
+                return "-".to_string();
+            }
+            (0..4)
+                .filter(|color| mask & 1 << color != 0)
+                .map(|color| COLOR_LETTERS[color])
+                .collect()
+        };
+        // The skip history is stored as one mask per skip, starting with the oldest one
+        let mut skip_history: Vec<String> = Vec::new();
+        let mut skipped = self.skipped;
+        while skipped != 0 {
+            skip_history.push(colors_to_string(skipped & 0b1111));
+            skipped >>= 4;
+        }
+        skip_history.reverse();
+        let mut monomino_placed_last: u64 = 0;
+        for (color, placed_last) in self.monomino_placed_last.iter().enumerate() {
+            monomino_placed_last |= (*placed_last as u64) << color;
+        }
+        let mut string = format!(
+            "ply: {}\nstart: {}\nskipped: {}\nmonomino_last: {}\n",
+            self.ply,
+            self.start_piece_type.to_short_name(),
+            if skip_history.is_empty() {
+                "-".to_string()
+            } else {
+                skip_history.join(" ")
+            },
+            colors_to_string(monomino_placed_last),
+        );
+        if self.rules.start_piece_policy != StartPiecePolicy::Random {
+            string.push_str(&format!(
+                "rules: {}\n",
+                self.rules.start_piece_policy.kind_name()
+            ));
+        }
+        for y in 0..20 {
+            for x in 0..20 {
+                let field = x + y * 21;
+                string.push(
+                    match (0..4).find(|color| self.board[*color].check_bit(field)) {
+                        Some(color) => COLOR_LETTERS[color],
+                        None => '.',
+                    },
+                );
+            }
+            string.push('\n');
+        }
+        string
+    }
+
+    pub fn from_board_notation(string: &str) -> std::result::Result<GameState, FenError> {
+        let mut state = GameState::default();
+        let mut policy = StartPiecePolicy::Random;
+        let mut y: u16 = 0;
+        for (line_number, line) in string.lines().enumerate() {
+            let line = line.trim();
+            let invalid_line = || FenError::InvalidLine(line_number + 1, line.to_string());
+            let parse_colors = |colors: &str| -> std::result::Result<u64, FenError> {
+                if colors == "-" {
+                    return Ok(0);
+                }
+                let mut mask: u64 = 0;
+                for c in colors.chars() {
+                    let color = COLOR_LETTERS
+                        .iter()
+                        .position(|letter| *letter == c)
+                        .ok_or_else(invalid_line)?;
+                    mask |= 1 << color;
+                }
+                Ok(mask)
+            };
+            if line.is_empty() {
+                continue;
+            }
+            if let Some((key, value)) = line.split_once(':') {
+                let value = value.trim();
+                match key.trim() {
+                    "ply" => state.ply = value.parse::<u8>().map_err(|_| invalid_line())?,
+                    "start" => {
+                        state.start_piece_type =
+                            PieceType::from_short_name(value).ok_or_else(invalid_line)?
+                    }
+                    "skipped" => {
+                        state.skipped = 0;
+                        for mask in value.split_whitespace() {
+                            if state.skipped >> 60 != 0 {
+                                return Err(FenError::InconsistentSkipMask);
+                            }
+                            state.skipped = state.skipped << 4 | parse_colors(mask)?;
+                        }
+                    }
+                    "rules" => {
+                        policy = StartPiecePolicy::from_kind_name(value, state.start_piece_type)
+                            .ok_or_else(invalid_line)?
+                    }
+                    "monomino_last" => {
+                        let mask = parse_colors(value)?;
+                        for color in 0..4 {
+                            state.monomino_placed_last[color] = mask & 1 << color != 0;
+                        }
+                    }
+                    _ => return Err(invalid_line()),
+                }
+                continue;
+            }
+            if y >= 20 {
+                return Err(FenError::InvalidRowCount(y + 1));
+            }
+            if line.chars().count() != 20 {
+                return Err(invalid_line());
+            }
+            for (x, c) in line.chars().enumerate() {
+                if c == '.' {
+                    continue;
+                }
+                let color = COLOR_LETTERS
+                    .iter()
+                    .position(|letter| *letter == c)
+                    .ok_or_else(invalid_line)?;
+                state.board[color].flip_bit(x as u16 + y * 21);
+            }
+            y += 1;
+        }
+        if y != 20 {
+            return Err(FenError::InvalidRowCount(y));
+        }
+        // The start line may come after the rules line
+        if let StartPiecePolicy::Fixed(_) = policy {
+            policy = StartPiecePolicy::Fixed(state.start_piece_type);
+        }
+        state.rules.start_piece_policy = policy;
+        // The pieces that are left are determined by the pieces on the board
+        for color in 0..4 {
+            for piece in state.board[color].get_pieces().iter() {
+                if piece.is_set() {
+                    let piece_type = PieceType::from_shape(piece.get_shape() as usize);
+                    state.pieces_left[piece_type as usize][color] = false;
+                }
+            }
+        }
+        state.finish_loading()
     }
 
     pub fn display_board(&self, board: Bitboard) -> String {
@@ -369,31 +964,23 @@ impl Display for GameState {
 
 impl Default for GameState {
     fn default() -> Self {
-        Self {
-            ply: 0,
-            board: [Bitboard::empty(); 4],
-            pieces_left: [[true; 4]; 21],
-            monomino_placed_last: [false; 4],
-            skipped: 0,
-            start_piece_type: PieceType::LPentomino,
-            hash: 0,
-        }
+        Self::with_start_piece_type(PieceType::LPentomino)
     }
 }
 
-fn domino(s: [Bitboard; 13], al: &mut ActionList) {
+fn domino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(s[0] & (p | p >> 1), 1);
     al.append(s[2] & (p | p >> 21), 2);
 }
 
-fn i_tromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn i_tromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(s[4] & (p | p >> 2), 3);
     al.append(s[6] & (p | p >> 42), 4);
 }
 
-fn l_tromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn l_tromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[3] & s[0]) >> 21 & (p | p >> 21 | p >> 22), 11);
     al.append((s[2] & s[0]) & (p | p >> 1 | p >> 21), 12);
@@ -401,13 +988,13 @@ fn l_tromino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append((s[2] >> 1 & s[0] >> 21) & (p >> 1 | p >> 21 | p >> 22), 14);
 }
 
-fn i_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn i_tetromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(s[8] & (p | p >> 3), 5);
     al.append(s[10] & (p | p >> 63), 6);
 }
 
-fn l_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn l_tetromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[6] & s[0]) & (p | p >> 1 | p >> 42), 15);
     al.append((s[0] & s[6] >> 1) & (p | p >> 1 | p >> 43), 16);
@@ -419,7 +1006,7 @@ fn l_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append((s[3] & s[5]) >> 23 & (p >> 2 | p >> 21 | p >> 23), 22);
 }
 
-fn t_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn t_tetromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[4] & s[9] >> 22) & (p | p >> 2 | p >> 22), 35);
     al.append(
@@ -433,12 +1020,12 @@ fn t_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-fn o_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn o_tetromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(s[11] & (p | p >> 1 | p >> 21 | p >> 22), 9)
 }
 
-fn z_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
+fn z_tetromino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         ((s[0] & s[0] >> 20) & (p | p >> 1 | p >> 20 | p >> 21)) >> 1,
@@ -452,7 +1039,7 @@ fn z_tetromino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append((s[2] & s[2] >> 22) & (p | p >> 21 | p >> 22 | p >> 43), 42);
 }
 
-fn f_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn f_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         ((s[7] & s[1]) >> 43 & s[9] >> 23) & (p >> 1 | p >> 23 | p >> 42 | p >> 43),
@@ -488,13 +1075,13 @@ fn f_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-fn i_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn i_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[8] & s[9] >> 4) & (p | p >> 4), 7);
     al.append((s[10] & s[9] >> 84) & (p | p >> 84), 8);
 }
 
-fn l_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn l_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[8] & s[9] >> 24) & (p | p >> 3 | p >> 24), 23);
     al.append((s[8] & s[2]) & (p | p >> 3 | p >> 21), 24);
@@ -506,7 +1093,7 @@ fn l_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append((s[10] >> 1 & s[1] >> 64) & (p >> 1 | p >> 63 | p >> 64), 30);
 }
 
-fn n_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn n_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         ((s[6] & s[2] >> 41) & (p | p >> 41 | p >> 42 | p >> 62)) >> 1,
@@ -530,7 +1117,7 @@ fn n_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-fn p_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn p_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[11] & s[9] >> 42) & (p | p >> 1 | p >> 22 | p >> 42), 75);
     al.append((s[11] & s[9] >> 43) & (p | p >> 1 | p >> 21 | p >> 43), 76);
@@ -548,7 +1135,7 @@ fn p_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append((s[9] & s[11] >> 21) & (p | p >> 22 | p >> 42 | p >> 43), 82);
 }
 
-fn t_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn t_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[4] & s[6] >> 1) & (p | p >> 2 | p >> 43), 31);
     al.append(
@@ -562,7 +1149,7 @@ fn t_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-fn u_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn u_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         (s[4] & s[2] & s[9] >> 23) & (p | p >> 2 | p >> 21 | p >> 23),
@@ -582,7 +1169,7 @@ fn u_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-fn v_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn v_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[4] & s[6]) & (p | p >> 2 | p >> 42), 71);
     al.append((s[7] & s[5]) >> 44 & (p >> 2 | p >> 42 | p >> 44), 72);
@@ -590,7 +1177,7 @@ fn v_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append((s[6] & s[4] >> 42) & (p | p >> 42 | p >> 44), 74);
 }
 
-fn w_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn w_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         (s[2] & (s[3] & s[0]) >> 43) & (p | p >> 21 | p >> 22 | p >> 43 | p >> 44),
@@ -610,7 +1197,7 @@ fn w_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-fn x_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn x_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         ((s[4] >> 20 & s[6]) & (p | p >> 20 | p >> 22 | p >> 42)) >> 1,
@@ -618,7 +1205,7 @@ fn x_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     )
 }
 
-fn y_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn y_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append((s[10] & s[9] >> 22) & (p | p >> 22 | p >> 63), 83);
     al.append((s[10] & s[9] >> 43) & (p | p >> 43 | p >> 63), 84);
@@ -633,7 +1220,7 @@ fn y_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     al.append(((s[9] & s[8] >> 20) & (p | p >> 20 | p >> 23)) >> 1, 90);
 }
 
-fn z_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
+fn z_pentomino<T: ActionSink>(s: [Bitboard; 13], al: &mut T) {
     let p = s[12];
     al.append(
         (s[9] & (s[5] & s[2]) >> 23) & (p | p >> 21 | p >> 23 | p >> 44),
@@ -653,25 +1240,49 @@ fn z_pentomino(s: [Bitboard; 13], al: &mut ActionList) {
     );
 }
 
-const ACTION_GENERATORS: [fn([Bitboard; 13], &mut ActionList); 20] = [
-    domino,
-    i_tromino,
-    l_tromino,
-    i_tetromino,
-    l_tetromino,
-    t_tetromino,
-    o_tetromino,
-    z_tetromino,
-    f_pentomino,
-    i_pentomino,
-    l_pentomino,
-    n_pentomino,
-    p_pentomino,
-    t_pentomino,
-    u_pentomino,
-    v_pentomino,
-    w_pentomino,
-    x_pentomino,
-    y_pentomino,
-    z_pentomino,
-];
+// Functions that generate all actions of a piece type (indexed by piece_type - 1)
+fn action_generators<T: ActionSink>() -> [fn([Bitboard; 13], &mut T); 20] {
+    [
+        domino,
+        i_tromino,
+        l_tromino,
+        i_tetromino,
+        l_tetromino,
+        t_tetromino,
+        o_tetromino,
+        z_tetromino,
+        f_pentomino,
+        i_pentomino,
+        l_pentomino,
+        n_pentomino,
+        p_pentomino,
+        t_pentomino,
+        u_pentomino,
+        v_pentomino,
+        w_pentomino,
+        x_pentomino,
+        y_pentomino,
+        z_pentomino,
+    ]
+}
+
+// The action generators either push the actions to an ActionList or only count them
+trait ActionSink {
+    fn append(&mut self, destinations: Bitboard, shape: u16);
+}
+
+impl ActionSink for ActionList {
+    #[inline(always)]
+    fn append(&mut self, destinations: Bitboard, shape: u16) {
+        ActionList::append(self, destinations, shape);
+    }
+}
+
+struct ActionCounter(usize);
+
+impl ActionSink for ActionCounter {
+    #[inline(always)]
+    fn append(&mut self, destinations: Bitboard, _shape: u16) {
+        self.0 += destinations.count_ones() as usize;
+    }
+}
@@ -5,3 +5,12 @@ pub const PLY_HASH: [u64; 105] = [7409293027115886270,8894192201332574676,126840
 pub const SHAPE_HASH: [[u64; 4]; 91] = [[15856838528519820744,2449446784980714862,10667262987684482567,14354809448470049252],[15747272450162180306,4961696156746944532,1632051901598976884,3327045159432397629],[15479885141832622229,10878355024663610466,13292771478585712902,16225208918611706194],[83073106887513080,17217828380358874369,7625918855685960712,3624399712677192810],[8580028818255747625,11241097192618467444,13842782407874392812,1657216565570647389],[15116749229047641485,17862640797969030448,14170961782655532797,13151062482156371816],[981883039368357752,8252148172102217774,4760079388243609476,2963643066660170270],[15111334636174984912,8813641237342207751,6756007753130592722,17971577433782050269],[2668701961113335866,14736700362632938804,13765821609569893351,13696612537189523349],[3799683767469358769,2865524926083086920,18108867000626492123,18101190768655200610],[16918892883290002276,4976292219391891100,9346767501383040444,8040636448082572481],[5713692110048063706,17782156984587492706,15959667618779016528,14046689945516750971],[15089924364807267136,16596258732994805925,9970700044851999012,16299235177301217561],[12768201930169188698,11381067485537798799,7389802127632149107,17035124983492950710],[4454016733688042290,1735797743797154174,1837531059549217896,15641131284945129336],[11520589041747731062,8634276604197923102,1186663517485389705,20123607458736871],[10089286166451241401,10921663681411654170,6562715614608687379,12242974031207929943],[365159098123754456,6681714013862808944,939359685637316132,3357625067406827877],[3313667800381129924,7471322712307065383,9443632747725445668,10345419598978135083],[15860230221247666638,2392373207918517403,6124910429832293398,15065215128810367481],[7963152031485284694,3689935459300187334,2656610447625133535,15255333513839383856],[15681828929114968148,17133756361490966893,15174518066426268734,5650153134959549993],[1252009027596945930,4131255752013509567,9905484773584664969,6033711508692365467],[15619114147965583194,13955292406494908379,8548969292516687475,2054393348972581872],[6657295292462550470,5678171690896778901,9765680165145109727,7113133819780762371],[5764903740712004367,17334915719856299710,10179100980519585614,14428565764368173375],[10753777522634704291,17354994821515713135,4569528423971556598,10475120135224796725],[10444717254021202774,13428596852489254029,6130583505509637394,10805846079611376169],[8089296566133308241,279947444997229893,10876575355374270432,15394134162367255953],[2628237896310600109,3990503247968028906,12138936401743947405,10265106567566356921],[17689057051260939619,993084666339845092,12427308677129200946,12855847557888620368],[15425585335622787423,2646305837696511844,16236923236225094890,2412074911708825792],[13275978260922162651,2584774129322864946,12319612210499242792,806513855425680317],[11463603045311474227,9187742688624589463,1248085399895433388,11003036801488917590],[10677662367706760936,15609493262846879684,396948002293980466,10509306325581409525],[7709470502105559006,6461642139172422830,2406197272652080222,18047501190982426434],[1348719851697728503,17683004070198470609,3624110225570153069,9709677373556764628],[9308873319929782580,14445105425368156181,17897737158902628861,11785702084224906356],[2228287055949262079,8081056381768919364,10949380366369532291,16066780731927393887],[6313297354061517341,3326003343394373475,8426221419235817251,1659438232668850830],[11998073439931194589,7383478897267856397,12853484435291622486,15462662213264063012],[7018150730877117077,5344948814823362982,8583541223216620682,14746100777229719183],[18154654171030616916,544194075427798256,2433234049548190992,1041793165544047088],[8599366685736616200,15644716090037246004,11056013269058583095,14916517860568460157],[3793445524706991361,12326078008277349087,694400024211930913,16065121842444865899],[5515713267747051226,2545740517928010702,1696137233284197523,7398074048272976251],[4600268487710775710,18275301656313957593,2387118245820348132,9252934119350913450],[149523367839034632,13475263271250678011,1431957174250370864,3955672941069577919],[14629205509891056248,5475416208061000944,14549749438826134079,2589004349073502371],[10361423900103534774,982334592392696543,17156381462001317993,7731840910279584516],[15348118379479859939,17657120073060885851,17020999670564528740,6557290656705594070],[1749748612805048794,2020098012888140681,9130643308501129775,5278527793044742782],[9030669215135871605,15696460762734431910,13523648006784626066,4874915483685699239],[12211770031394699510,13926958551584898019,9001281271462166756,11688646394867322300],[10851133797300214598,14139458788575334808,7074999652765359410,14280707258552138355],[17424436654842784309,1311670021296665825,14714849982090788648,2376202992191269054],[17254882805819462796,17635434172027407027,2794559030149240826,4157502855694725122],[8619958908662306951,17856872762317743553,12167751911806873252,1181821173687458730],[6540428998211938277,18175513768175245100,484194247212244632,15961201232327949845],[16159331158311116551,13617252531479570924,4408638938896632899,1155464077474985466],[17626767654735382425,16144130198753857518,4885774631466113828,445988356629250087],[17165212224162537521,15536432205846018107,14235491120090390684,3375797622744574431],[7847492639921220117,2152987996315738206,125439253136458579,6836563974160122464],[14742979963703229961,4402993247436028889,7558875944411427526,7105369448642540948],[2289253252306809377,13550842222117827244,7091481972714245640,10975362580418185711],[13765126797526526116,12065515353214390924,16003049100809565808,5553945659733217307],[7859835505189593492,10582350438513836061,7279022684675159560,12371065595120332106],[14517460684396635985,1905359922312899067,16017483252122227023,4056774704996980638],[6476088151079655734,5189150742871166132,16173431697923167071,5833258496237030699],[14836798243101103276,674127109322939878,17528200802804830835,4007568488917156583],[12113229757338093262,13095004303088382284,3693104345200823665,11283586492065316697],[16560901887315480112,1704321136303314735,14461150589330932603,9088583787223576168],[6052441205273431864,10957604829329296615,8163459545191675369,4778794222770552630],[8616290045352713984,17062722192920476974,11669486080058125818,15342599644851522321],[14192114358506160637,4749544762755712526,7209565211630643700,4977230134654616370],[9334387996916566487,15690116272070562454,6408707266367457520,6804128701762789145],[7403209081092280678,5440578488503064031,8965510332909828245,18159521198689621657],[17083846509489769602,1915778278966526361,5168625427528847719,3979408582924931022],[16098257041010542784,317231131118418260,17932728556786924544,2624612261650970094],[1177051354502958365,8607512873745778990,9144497678030072072,3192184515926879899],[4368984285105271479,15877012075923220639,2646768868498252818,4264576365687562991],[11288281918935610203,13255581544084216501,1842167738103652986,1974241049133226845],[9664070837920034250,14972392416284975307,13793069224333526156,9761002869664300454],[484368111518125775,5141312571845825786,4432649492904884087,6534451780130437516],[1294329066436510852,714386063952880883,14129211794995428769,13496599859800200024],[2294670120425383531,3505324414268819401,10796882306896821023,16446015929886004560],[12933390933025780230,11418484567620824511,2840931906208362970,14282805306838552379],[14806926501986364750,14365434543844325574,2046398142501911135,17149109086056524206],[3139085983657086399,18350446753109599710,14252776458307126092,11432592168279488643],[15619338112317386935,7184688572219463148,17674616805296774869,1132338815810098384],[13413322243534862400,797982512636679851,3730983102609781988,16993959642556878180]];
 #[rustfmt::skip]
 pub const DESTINATION_HASH: [[u64; 4]; 418] = [[18343552465972379678,10894195965558592916,1656054058616573062,11122853527496609009],[2233742617740305626,10328701067392398417,1915374665500510113,3282439039354359668],[15930742772869836350,4442867854935099392,6868565000220328520,14206361272786036333],[4692129368206083125,18030504408567009088,8790113406010110322,12276458523084996087],[13438552727513170723,10889454966996988931,14016430683888249843,1684151613615320840],[11722421832208751265,8174758571324622865,6884548174429850318,16086124131249034612],[6003361498902438942,3860958094608782332,6685479820616149833,10375071663724722876],[11843619400877035916,7486675060880700802,1097888445409317484,6351131388090957286],[12552838957186298537,1423716195307447092,18187586926519375713,8655721120314745280],[16532199901555581034,16989267383830718954,1700297171489633047,13394779919407722204],[14289637158809426684,7696988996541477330,18445971291864847580,4324724571179806735],[6717537709962854199,15424641771725413474,14387071856025750176,12438658954413392928],[15836230118836582916,13792350021198507745,11168545932381409492,11522050928761643851],[12350036167470013200,286805147856410956,1606640280387356546,7055252121546948557],[11493940665617685762,17654091672689909493,15037556250463700392,4192858797642358908],[16288164314272554978,11772788267449187424,12439673788898997743,3221727206984944629],[9968233405688431514,404923706239468512,7547971059865186701,983694876394048433],[6758802591821264300,573774553175955286,18382207820871147073,11220941722450766168],[10088561340847546506,303921556743592259,11864186912546614983,16108937302487559659],[13361427127669932629,1986183657238134915,8495335447719093536,3419853922492996158],[6629013897954925859,12057801601638638175,10201776423241406881,1012196410471172759],[8029247859815970243,6625661304179095270,12875231659803201983,5855641211798833146],[9953763159121930603,5660368300137891317,13610752259102319103,13184912938939423484],[7676969663342333651,17827486823316789760,867952851504357370,11060537776513490389],[7711335424905964312,13141907318052760518,1613166078852244554,18342542334552463069],[12325834980209280639,15677390162745784432,9630149242371812178,8567247436366039875],[8382926184781369310,13849792272781617236,14743372223757145832,17533990083185111817],[709278193508712264,1815904176183573393,14235781211538277130,1698388412131121928],[10067101970865367991,10723290588050343465,1638809774978033512,5993325761882146581],[12077160172692049844,199635301555558490,7429858414861217639,7440334252716876679],[13792585028189044174,17906995785955483914,2604370837814460839,3438235874363561506],[7793408748595640745,4386018892548114923,850130254835814718,11633769793135926481],[1172264935836491336,12906032970031574147,8715214270071600461,8731236473508749066],[17660838773382377531,5263735764493565205,9663154260209086428,3887129289714509521],[10690984023204870981,12688976357700487951,13841519407598150656,1059512805367969382],[13470854750628750812,16400299207616486705,2918151031041190328,9457754772338536097],[16371851792402580353,17824246526113779937,9840494249947945183,10002132741421823901],[13434382112002901866,11171660627328438210,7111559326737807418,7071846562408787503],[15950918952220628259,15589024283208448251,14486771569587549569,11836421459859279498],[3314649379866600353,1915732437587829907,3466321754022517819,1870433915240479711],[14828606693280482819,10973195417027845852,2904457078216902976,11551181505286841096],[1565341941939482874,18089332394452091085,15734274929437338918,13001700469909726725],[9369449718366407196,12649160419867007393,10827350577964579788,4818108733313898402],[3806803413410952390,15341209257583429235,16662346517343279626,13144065425342808776],[5603453094688442588,5909811560551871261,10082065956368842320,5190500082826361209],[2899971902386686811,5271511871490894722,15841225322460783817,3771384413896340603],[5613931147327565549,5389473775782465539,9945907820425949826,1231808843867737742],[18274103177881078432,1125327401248880558,15287399208349420231,15724316572969476431],[7280099133219263080,6816591020981598502,1935441820719535864,4199032249565501658],[7918681409810224016,6636906274352962610,13977261743920953705,16113797457834956311],[13901372920485876261,14338307547859783943,3892634112298399566,17637663955239409756],[15286562173318156385,4126070934588232607,4529894648987948809,2545243015070338625],[6667785415923511349,1160126787711737514,17058836640006104932,492388429603595832],[6356033698393395436,3086387486497546688,1141982806763127769,7917638083140588537],[793468852451642781,13165755958179754819,8455238287445819619,4739400324411144649],[16166671465468878642,2055162052686074184,14845155774782839120,17262510463536328595],[7111214216292978765,15319981151247647482,16975871470274185691,6993188168469999134],[2270307518487479824,17348693470814468156,6498422116692783124,1134728358291183461],[13881935416801593085,3283763094258309134,10923869409168946807,4742941951484077096],[9059720186739537108,6449308343193512621,2611546809336345271,6693961255992069095],[5030406541857024032,10063809014147399527,9134932767505838369,10047569714719593534],[11097510792467992816,16462546067864552997,8708805400191702010,5981188976780754435],[12329412028968757980,14721286860102400434,11792073042199296922,17530250591547636855],[2777221974139052801,3983121769298206203,13332872716436858881,2674534659486255035],[7334641025711386369,10826284892541764416,16205368815115882751,1025269717626341241],[12241884905353546984,5314601815725867185,3937528772485047212,12203238795877811837],[10034738337682606457,15561640209873714445,1434880553829629780,16710416898595775309],[9005042990639533280,12367313207474116473,6298627349219302347,17383929175830044471],[8300472468639878742,18153756087859036890,4549281146534248052,17245068208181530369],[7570166731811289755,4864112831752536651,10751335813403534710,10618918036374505443],[316829351106210421,16904068518724190039,9926316772673400320,17571194278898969534],[6135375004242734401,4560251504761069906,5129647019707387887,17038575428401885309],[13570953617598548,16391657721935436695,3143651049028491341,10164510056417282639],[421299022418600857,18207489627126685906,3337333419800586029,2950369410417536892],[1379351120699180921,16328319702214252417,13539159742707330147,13282440185174514653],[10298398344152206247,10367091604346553922,13044252172191256904,7892745549694895603],[12750285633398768709,15249967835118413409,11426493392118160500,6409727315493094949],[13197103694760601955,15815505521227624585,3276639749212602697,15116625689587318226],[1151443515763043057,8008883964682933036,6578455129352549603,14897050543686090802],[1663742149594599580,15990597234820948404,2771909798306431365,2312533597062549822],[11861883664756537940,3710511394364860232,1508647263688898388,15933836410437190013],[13402298375474652820,15715993207129714390,17938661912490286518,4325671981694643882],[7451829734997606008,9192934966730574258,11304948390710650244,3409325744999183723],[17245583013888579702,2970520640932514049,2665950379711416655,13124549815805576044],[11514045115438459120,6375370954571719107,6150509102754273521,6507644120835276920],[14233639961362511743,14160240148924542065,6409101032820776461,4637728429060442210],[7175343833353237409,7274882647102499287,8896005532439201887,8141424676913137978],[2968586951331287411,12546646706432355086,3016875083087801846,8900205859519467671],[7725038662932534343,870365271102656,3798629324702484689,17112009565304730519],[9205694261679293584,2008569825103880309,6012063110433003332,12713709769895935196],[15222832842668339640,4898444293100636282,12278856948672828809,6568849798202359756],[5467074357064687829,9488081751038365166,6580226335381455660,8450277942893252584],[2522816044492067671,13420291942015728593,12336872444927820315,11327452111139467260],[7330916287977212665,13132961963775620455,6900125665653456661,3192954599971584039],[13526904801239469903,9764498048227356405,15686295165312297307,240308545051004769],[11676525540006001647,15986652741990156134,440531482769454535,12995661294802135995],[8169161736874412197,11892577902650661160,5332275034526459802,16785194989987037782],[10174038953007803683,14932691627545492951,7553861573037163234,15577648527233504372],[11264472594262082626,4903616286943453333,17817277753316385227,15250831528136696701],[6453072861314934327,1378961984123722536,5272931381728259605,389922050461475716],[11258287816805664031,7764016471533791201,3914562631068386331,8847329749031164556],[10035593433970785754,17791388642284609360,13202381320859969416,12635774590946787590],[11027345332883178930,16747247960587151779,13979757508264491336,8902598334078976460],[4257576138443940826,12076957900753695114,3971299122908044951,9792696257346511689],[15979139146795042070,659087216118437969,14453973666968185468,5738328314624330652],[8966570961283831845,7012359172338957346,15022490091478543881,17600123483800092045],[8161370000305847830,6439633057567188973,10294615890196423239,14943947700540422729],[697027784996881915,4344174296515660455,12681396426683153788,4605704074746536018],[10171331064227741491,8118661342830873340,7993836864768419002,15615275551287662447],[18082419423892137745,7950059382064278885,3270161081667531942,8951711868860871006],[16579619231740223890,12712387011421226003,2961567669086373685,16059232542103404888],[8913097897000842141,6349291735474219194,9560369617060851813,11010544826972003973],[13681367869043934233,12488130955226654995,16456086928066102506,7964080936457527606],[14264491931731130403,4921320866003635152,12305254308409270483,4118621944035365527],[16377147845292204714,12701944289088022921,3817943055798539414,16113490382177955227],[6792817639484823409,6999143544970950882,784417607357846667,6392584849597103282],[1994229953988722925,13381612160774854084,12107656197521393875,7203745485528835210],[9635687348256378166,18290328650709081519,2372351818530019998,3795780067301723019],[3260700637515077515,14284909226528653511,6267379858280597625,4440142614075394084],[7490584153225789220,4551450088785156917,14016960415941706225,12487876723798838619],[18389601665330248677,6213333747666753531,13346714033688469515,14312220067669282672],[13723254021793932909,5015791506952420516,8920287343014588615,17431310671917891433],[5182195168040635380,15363589049378570724,3060855939419342795,7773613257432517143],[16887667655254335588,4775272361724823161,13266044965423545726,16800671746361984358],[11438745422328045592,15737024721129297249,17248740721922871285,16178389795003343558],[10060994283724523380,7732173081348790048,17359638944117958146,3149757528461430094],[12310432547858123412,5614631587084014812,11138298428898580698,16936664454175425870],[15840263972860037136,12790002694763555834,8219715151265011212,17640242631339031538],[6400511216824854684,926480653022982974,10591405893323868535,15730819508737435030],[16823344242791799978,14384594420940662726,8867425906860280378,9212163579045329104],[11683520774272324955,18211334465868475896,9756079014442088554,9020239586383489484],[12467515600311140243,1987772187223237696,13238833715843082034,5852057395778456127],[1744353652751405774,4129912649747231994,15298423218427571125,12593789367239308554],[6565415471792656848,7402387177437570744,6511308685540279291,14543722952651526932],[14704916608056112524,5052791984402930188,9706157616756852502,10627060075431011947],[9139752686003776643,1143890746112013267,14588689840054416184,1307318457937948598],[17235201910860341976,4318619433106358525,1140608675276545541,11242351113495940003],[1499877283348456609,17285280491275004613,293247355545677890,8448595743602173021],[17453071276459135603,17123566652732614516,7032357420132979730,15843306638227392290],[298540961517406846,8359983438103102452,8715837677870413267,16120284646961000730],[3874263152826331160,2779917743151495609,424802597417621192,15686750869547853907],[3633970955761300682,17920639069461008565,9124221724199501296,3548807786835484394],[13998954956940299972,10959852856053298402,7806127190980589765,10045937069268683138],[11044223584119249232,5521570874513038841,12445090327980175254,10763847321309987990],[3862967893738530909,16382700579567119132,3882116038117684571,1467950893841215058],[5196323865185507351,11175986138715293382,11243217686934981572,15409718422034720171],[6515823866271728928,10432078530220841477,9230005389303826069,7870553137899242789],[16392972441915276252,14624369093435768969,5262652774432606120,836403669023613051],[10979578506042691869,8492197120936421023,1579040110033922582,15523131805530091747],[7262902106182875978,9695318397996698342,2466825374382273842,14138311809340771129],[12868305820314962880,9248004468509532453,16776032246089479261,5253559454267603966],[10893160593304379903,13175630728412760291,11468969886641929441,17358488768147269387],[5301591750464788508,7364735282378561343,12838683018773927088,4211050295217856333],[6711615486848798195,11423366790587991705,15133590060886239303,635301564606723288],[8312096014459103717,11779705059788676684,16488101430824939105,15258158124308991429],[6609441655990008877,11469455241858513291,15049099503717199584,10376450532481011721],[2875667637231239081,3762681325364850482,9569707303612611591,8707055030692434335],[14341652242125541710,6184760738442429642,638004999129446732,5871484951002483823],[3028741979596836822,14300087173869023857,17332046490101499640,10126481650553871778],[6661766153556306488,1048659880680262803,1898764560692899904,4192480965593711685],[6810152671606808594,14880672249203428708,13643763906817366307,13873202278895809189],[1677775708249252779,12080738520564788518,6105372995907057803,6503597021234995907],[1396779617189548392,2274047375563190329,17048671261763040594,16617107332006052251],[15301983782473919849,10067398086523253187,16854921387353945830,9260834301376340162],[5280785628587626335,15130484116715039650,16288991621778012756,582221654000411217],[16765251458426737338,9188868250282613984,8094686902106865345,2260731215657458302],[1065412216874866387,13508792787743773216,10932382297322182837,6076275017377561208],[4202867223164808359,15476396604960999360,9708109774213653412,486686872497086591],[6048957212503540926,13099836514710913587,5432422862097279192,1431672563116277619],[13257300252127855505,3969809358755100080,12282270796112159880,11496434200007767752],[839548874613890306,12697495235010502612,852037748430734895,2871367866565832612],[12351424068041875908,2214842187458848688,4916876756107847337,12699192212385597200],[5794539131585775715,1452890201919910575,16047327006268488101,12602564317363401956],[1686736112226701766,9993828540609003474,15745314069599716184,12487903865043015919],[8469669164486482042,11028382630999115884,11463537517113927444,15832245954729017339],[7159974110439629617,9997781063911009046,16035497966466264637,502159080503142371],[13608389861825492107,17664254502025493002,13262198888591230301,5006509330264786160],[17704868500866158679,6506058321612268787,598372872831686767,7619881753970311628],[4407631914931011797,15457770243562518321,2611543514271981446,14872413470559953221],[16279478720965127369,9222735608167828173,8918186409440951567,17598201480825673873],[14592654269458654435,3976287156578224762,11898793016107044602,1921624187354888458],[12709006516590594207,7835199085150821733,10170276427766241200,11515583019123626864],[3318078999233202063,14386951659269712333,7246474549135667056,5735683716129448149],[7365893712917164893,15875624148219786925,9082096984888025385,1236347623838540536],[8606701438188931633,15408201807580737897,4093816740551601239,338765506391412309],[17854585268418656153,15958995560273985044,14169677487289206212,18187649143000326995],[10709001052886418310,17985894981238228928,3332873978587962188,13540168896347810320],[12707525477251291501,5136631142759855266,15687232375244922484,8660354893733120836],[5360454842980653095,5947432620924021249,8485424244487056899,2927907570730615866],[8709149277764594813,3257943835812855601,7529946553174313273,17256141426681656275],[7009585366237464429,15081289617203555716,3759969260564137160,11579694845478572103],[18154164911343388611,5778012739346894600,5015359920651452965,18186234460843831030],[15925800450883930196,7943167626822444894,11712484358471696957,18210899839018226695],[13699980412608280159,8210638741892163901,18082453232896784473,5186728343124234395],[16254802671354111537,1021212442644349783,6305786943024852208,4314069162420702808],[17943790781319843976,14848640263470213612,4985290548801298015,9325594063366875499],[4974521743883816763,8018536575375305297,4242727933948237543,15169329763919270386],[7808478064176184901,17566177086706052727,1563592409559212724,16333138981488191887],[14899470870223264404,6021279782081325976,13174450327876225810,14281773853549102474],[16367168555399306666,16337365817334099724,5373616543873322290,13095680223275556523],[11235503163187514690,7797374881616224920,2766797221129775209,15884704170229446691],[1278776725808881052,2032561316543709902,14510498557632646009,14903615416117411718],[4936276105387009567,11709091497342713495,14567742267536953002,15459036098883789972],[8967772716525070808,14119895115686065963,14761403464469790249,12928461261874319174],[11301138551654818657,7124679768655883985,3261019285822144226,15428321351423525769],[312746095446180934,11695372191257626531,15370522366509851334,14516509922878579109],[12963573336988388675,6037762382630846348,4883689567973695769,5524416347199080510],[10684130291098348765,3545325679808659605,8723997073168133024,872935527962750358],[6193639379436116705,9288077657659346378,11138862490181875689,12747925796534279399],[15006260612569117154,15930134868271439654,1138256956874002013,7328003433654389418],[1606904582661121319,14556788882534705549,4547688762636252575,7958638917862231711],[9829352477391586747,14432960451667211142,15108430353304622775,2329400968111217392],[9282485054006437234,16529519307096528837,2682761453016919077,1191573120503365560],[17530523449835856345,8831997545915402147,6500239067674478576,11194093586539789986],[17565686336333592435,14723059168760276819,6265210130698612029,6628242634283661521],[14730703094980645711,15221211139227021477,481945336404009553,14897574834817483764],[7876709913052204069,9411002610588414096,3055115664741596196,9643738994703696428],[4394978676269575090,6274977441265805456,390670349614953820,8000585391427087778],[18174633031966738602,7526023317084327554,6011980963524599469,17389951681525919912],[9808585171642502665,9046972806923360784,13389951087050127956,7224901072932511739],[15547815839558298946,2068101429770721665,10882061922329467282,9105144230124385256],[8910435116835693789,16863304789013997494,660507667574617745,6194324672980523949],[13322795788997338396,17650114066228328189,14138026084579563111,13117267880262995492],[11469666598296385815,13186502112339938108,17167706213069314056,5423905744379906895],[3369631909277562918,299649728307864256,1919227534504437391,7934174092972277088],[11400330840754962447,8475361762886435096,4125560466851416190,5315921661154184988],[6983280233798011634,13632532937551027786,17220726028028596902,18402201776105247148],[11303198070379121899,609259019379939638,14117661638064923359,4682167830577055171],[12125634012804682348,727216792105622627,8628059172401858381,8808539224460022984],[15326071153288575480,189085409469978695,16039754513321043449,12483022979109257049],[14056248901861022166,14578755750579724539,14914798938417399019,4212137391578230401],[5904365246588027793,13360880110697290867,12753447897941347115,18318235569460355065],[6992137810611578312,2117535300259970500,7768534008551240312,18359419593441185071],[7733748310408370098,11675509298621900943,4263068173121519758,14348224278243851627],[6575232601308747792,14255781846777573298,14525587719072225899,13809097221113801893],[9656878329276124335,4591277903432048944,2399079588209241204,4254727792416102855],[5907715700408459395,17280257124523744663,12576240706865403596,9121762815721079461],[2488753455971517414,17770834300389621268,5619158743391933155,4592778807974816327],[8132116931108165067,12696087198278649603,14177479017457598524,17485774214312731586],[11995868115020039600,48805223596924145,13259422208421124808,17169501910937704556],[6063564805711822016,12549093552747558933,17717857701806027780,9671504912536144635],[17354261870007220216,14991888483190526925,9143480702409297451,8489488171383960919],[3799543096714805195,15003223977560713214,3738732216575240719,5999785447975399569],[8064729808496684679,4803576651435471093,10121742324183070251,15189259378023220888],[2363107376469637385,18261885247774598064,4838779142233543857,5725816582246025509],[8083306911883629046,11758262724397082218,10919654913550796619,12319428157359501479],[15349830342916699071,6230354803754872912,17314844994052149997,6816388294139903337],[1659338975735113018,11758437763081174112,16868601062588374259,17060384896787707375],[3443522210635735676,7225570742988615073,6148209776672234115,10300002721676513847],[7567519385694541726,7923353173060858592,15075711875488627439,10086393535528364842],[10199287452095246377,5701520004502623163,13729622450148121379,7251875270585176012],[16262515711473574355,11006463363944044533,3634699608791625375,10338945861906172504],[6124744872779398663,16585978056419730157,15515397224370761841,15913044935620454300],[812964908296372281,13482901936761112548,1771125462855888912,8208940248647957737],[6419795258263403125,5679417548239540869,11954252448270219645,16552411367112107772],[10918706250303223130,17319037932057045677,16220307475287969606,4150677656551749066],[6581946743583966704,1647936222284914136,1744802503807450402,11316335936038824404],[2864064568549983831,14793018289891953683,11660752452949292480,17757774720844793427],[4629564921829141401,11548053896990007020,12904722264072226194,11323078278295858095],[4538302217178417329,6171506242942210123,5193369928258015987,11302237242496752666],[15299876384979394906,4079864884471239163,12444902763899329887,4860993114807560158],[532976658211471310,1682106898659106516,2820747102704488696,11980202176219627977],[418130246021346655,13207363086809982726,10389508164988622968,3472253222426684334],[15175010991963888052,6675133106558074253,13353223958193254242,15287571917368914120],[14534161614626163723,5306777095872726633,11318423862024848209,11540721302774971599],[11199958562592369782,17970785344799107635,8818741405085911383,6894569085955575564],[10448052171200647561,7851898656293454741,17533644658037008056,16718206677994986416],[10509241738499670280,18012293957785739767,5682516801707442541,15832497424417640038],[8790796547854075546,16116636548217188432,14061162283241078270,9908465974573483422],[11965710133191128093,1842682920078252978,895243546298389304,2690290153487810437],[14945613597710467321,15092370346453429993,12110420348740181120,5152363107238705706],[8722282757687436355,3254361839389545171,372173116023248569,2216105245996103800],[9204830741063845810,13582453795265976386,14995340657121176802,10925435298464715628],[8661244921448978338,12819235066799035182,788562027755000175,14450921781132210599],[14546854216990516507,11444710741437661092,7636175763917033773,4949554969755781065],[17527671508983348997,10423297151463165363,5366697121249848186,12217879701681838765],[14548454469875425026,7515758093571169255,15787848230776133115,16828201712278975939],[9740676891527771930,14047934915070420474,12403488052333371448,5667954535173845908],[6496533690661614697,4232915405662512739,18140391893260833721,4974195979278370133],[12522988197086486414,17245780125733049056,116384990853621800,4090809060284571725],[13200216053667783343,10537884994399831986,343695511293596646,1205179968756710412],[6864497114481946555,17772662375809034537,16663081711669615424,17490864260133419957],[9848727842935509565,13075303339224927870,12838011547427088826,7699361824488362282],[277901394406200079,1648231207994824670,10720026186993223104,15851772440517344565],[19348878015032604,10101737018812413397,16164774949904104755,5912982434717155599],[14750119552571334335,13224116434332714808,11512962200923561061,10412439327887560399],[3822465929643168090,4194037940330503936,5141053786535227395,15679323512423457599],[13552971119680838381,2895322206612176034,5591136078279669678,16988549449588730426],[11529072510421691809,11512239823199707392,12024374903699665311,3060357489017889486],[15975012290930586131,15604175189587585085,7657880757751657718,17550373839290261564],[1498370521039021105,18243055692826677216,7463358751085944946,2411077305852569709],[4162779155659918138,6430143667948613751,10108539087620984756,2155818308342937425],[2008769746326523025,9022815128485798685,10697457368028789107,9992628873559458122],[14897113182288720946,15718247922976798622,16060372353794923317,10998526614507920482],[4132294439123202499,10046563848581787330,1980099009245982113,4933529244328861489],[2351654726394042628,813819892389779346,13026389616471885394,6571753405690288862],[7365476411698035092,896097936761463079,6311803804173157110,12869229060877974593],[617517358477659040,6912652021622743441,4502146396533996293,8426380880141072917],[15279794646079932560,13526534148554966449,6793166918938720023,11254138254881241506],[2138840849042795789,16855912136951920177,4213154339543877205,3293747555610290277],[17108575334998006358,9989663056429781141,17202065707500767723,11587575208626650904],[12565158634972777661,14777016048268157770,11420019499773263157,2561854890410146300],[6957814183956365191,5590984841623668387,7629150841068805729,7133375349701862128],[12162324197918657380,15777540677689888043,18091002581910619805,13429103004096468992],[15127892418318796280,306669357969436403,14799456054491506640,2962753788180253765],[14561961507052960087,10486228806428975334,1061303393901162139,8952305286520409816],[4429235478026838276,3794706140408716061,4256312563078162514,17051188854774943582],[2000020989869979785,2235891024907992105,3020295626515884756,18278377909186847911],[12180307830719004621,594288620397652448,17316818460384084426,16282217521499973410],[18127697431362558796,17749484566755302323,5848166173554794343,2258953409524545804],[5815398553944480449,15515613578839940083,15559805356251363647,1468983605301978308],[978455645525138453,12979759256570550948,9017816028956228103,18320936223270256004],[10830844249320408405,5949802489449823582,5594951961579665349,12633729237397068541],[16799540581075390294,15303243990053470880,3227455050260266652,14725293054743456772],[7791284612439163224,16406105004838298713,6935442147677541841,2571910722005959533],[10165415192005221471,11976195630184311081,11475057211695253762,3220341041158035714],[12399278486868637513,17302030294658269700,12662691295666528212,17622985719697564325],[11869651349698143445,10048807861958687335,9152910573708050754,18389871278826650478],[4146223463071421268,11488672338484035874,3433202238360871612,3113850663848980634],[6053744507017946983,11161207135131040453,16638442244856287419,4674532294839928548],[9070835549776210371,7939288841553587314,8560354534262137001,7496055115913169048],[12185058920428788735,11787909902851796105,17708394024721895149,3390158240184726774],[8422165340585862638,13361029996025402569,12440735156067954530,18238758737501440883],[14688895737171968067,1733885783545158980,16292851573485581454,1883766798125360071],[1275586854375836006,1229058827125962891,2706311201378170198,15507103935591036779],[3339523751928254770,15001931656029563158,10244003357775878343,18302306909854817490],[1168514316565945665,7311604490283721657,12150263834192221223,1631019431052421708],[4850939866883784150,344792889003923254,9420770871595781979,5559554745152545521],[8391176790153583679,11818639775843474998,11228531909593212153,989161443051316446],[11648579932657057870,3182352954407789745,10694325012252987413,5759393302990398962],[9184136289736629082,8178572179646134508,11091519246311175449,9223204917473557071],[10748071592809773510,9747534784050754364,10914674485261759964,8949036768744354176],[6487026966093083052,5217761699465672258,13077117762929285118,12210783966666017845],[7400709773841745703,3211319348301170289,6592282777779377783,9319518959588856448],[2255795914788053026,4203735644847912060,17972244592159910328,4411847178950560265],[8694332514234582281,5806618577821612170,4253570838350210185,14908007025523504592],[10082133349133839182,5250768510254986431,15874321354879516517,12670828488574545712],[3088601439264761799,13970278108949023953,13541742084983224589,2125651312924783836],[16059657012941177744,6026305593057715602,911463485389942548,12311182781256368563],[1001150412377725839,16191729258495271200,10656929947942573480,2052731243622751970],[2627366737113764501,9921328800288100860,7190362651049744889,10679586790695654805],[6299143462684877631,9819736993817349521,17028075995155590874,15421169266880791794],[5879778387934237512,11508704364465824268,676628298751727353,1812690062605263326],[568327937510777005,17179644161240782268,11439310732370320774,11141850348636313671],[14943869606537661941,10962971431162509630,1155688084419776306,3755231372471407092],[8841947172514208696,11254807241136160848,10606745829531119882,106063121243653564],[4646722119516864986,1325492826830146466,16688268060307101568,18132275172908362726],[16060160873267827923,11108704154201801082,9566818607306604132,13756515976299416148],[15748715358819016828,15985214943728485417,7565225765291831105,13008413780803045611],[7248595875030034961,16647403244738451708,10393207637408145333,4327946242056106741],[8028883764141406475,15046772478283346973,2596959737640490953,14945301275507647723],[7713825327323337244,7671437411299471853,9909353245427074029,15369864973082685442],[4089113492323792187,1849610049852235657,8654255797263479628,15233652137059952319],[15986673500156986512,6085549876595832976,16753328406815065110,10587486666876836051],[2763895504625527737,4116297917869430610,11995307423242825325,11512398748194816753],[12798337802728766418,18113513603501895354,3560732875649493406,6247636524124086820],[4527428680145534808,7436433122580000838,3593879872124061582,17813655278560232749],[8651350363360762459,14142675408155409401,15805620489788995471,15137156203043823456],[3862183363004150623,16530751206674879688,9532880541735262470,976473049333760921],[7479433525371930610,8027861127528757353,6798760936719116086,12833979326885787059],[12430192752932035322,17686049578922740226,18011519448461341043,7165855926051484873],[7186219405323118091,12189318610424525761,13302897571301766357,14702013851025076925],[8970873389021108451,665622581778692017,17370211194150704927,7853611621291458683],[13664640656510253523,2538300250862231693,11723268192652111212,4524946262215727581],[5177879037988318787,3772723348811978573,1094967906432367501,13202265850234213997],[559783827714331966,1035959162725456457,6841728799224207084,10721941320640588905],[17080211432058900309,14320495920583081032,16209209681384779136,14898678134423758228],[17917860707519324936,3657562466775108107,2001453880832798308,6545606705446658883],[9608770380791534690,9897886781292919498,13793203443325468567,9116602857216778591],[6101278819415319427,1729750589511747019,9377653397652228269,558581211168370802],[17577468859058741914,12566451960685707644,17325169190378679369,3616974667748219248],[6860215131522355869,4826491657355936153,2448609785180600917,15952426735281667060],[4888911276167705968,11369742444122303089,13863288416586726935,3771393571562341808],[9881306593731362411,10405862058984537096,18291809067691550980,10585284463700403203],[8456659984907839846,7621508212612182325,1260442915592605477,13287859224735837396],[5048070944347801155,15559708929063918347,4646754110623397817,8125622699894845346],[7774823432221441849,5247473305190315327,4892232902958365436,144142920406103575],[4121993167367702429,6581526569813197304,17009348017312654750,1983057599697220238],[6135603446135100616,8004978499823177811,590794379596703614,18138807723942126455],[11900134860445141071,18139291250214373141,2857702116570435478,7284246895394491580],[15731070343294817695,12242194799105862036,1924251582816862253,3260897212385606951],[6313705312279278033,222108756468160452,16519195072605068530,6773663461253444815],[5193531759640559041,9439577422526494134,10537761813066861356,12804680748332276361],[8900936457837542294,10938862444911484061,806460804695895039,16848248930818643167],[4702256613656829284,3422152090046353565,13723798194785837944,12760126622581764766],[3832182335287707226,15314694057445336909,3590861816035266962,12863889656261959245],[5997221910558397456,12929006669649455421,1952195836695282871,12131651365729147333],[5370471144472128444,13734126589857616587,18306502286487380696,10574134579197427402],[5136192003412318710,10423840770916528215,8857657632263406630,144519620677423415],[13717299021572699313,9969785081171798410,9603771291214842740,9100361774954918626],[4430500688085331091,5744411050010962031,7282136346506005946,16537162601568603690],[3273738202203686223,8750715357339496960,16973368423450767546,12217216083675960393],[15788149818007174931,2917493771300735065,665295146876084632,6147171413190610233],[12885989190895378737,5732254026347276046,9455850025364020449,18160841953210414169],[4681391782253730318,15509934733234304809,16301788399523341281,3327629742519907518],[4783706252720010684,5213768175914085091,17231930462466552087,1700282920794129321],[8241704039987109133,2636767374665568337,471837071286637380,4436716024400245907],[11089078485952073608,3994836792208852730,16231318542757649644,864952794890626866],[7855085834796418369,5365458082680628441,14079242351812697200,5411499734137524038],[14198945453111226769,15104239461618077363,3474168610867399876,14995623145039576598],[5457516678639812013,3398669707486235772,11721205256475995258,10013029790448135406],[17709677149599947427,14376236516077875311,362081583708930761,4184219078249440478],[16631768124242385105,10395021803216024069,14374960593303984395,14374160554880234986],[13879808744716814698,8697954412348684942,364739065386757102,14696938300190837097],[1977270278565878299,388839603727686443,10110116289118829157,17512987960972716438],[17383871073937488446,12791583864326633020,3864261127517354929,11396862856906890888],[11167520567010436065,2667747330623050426,10665920338090797803,11247874093053229256],[12806328654918801379,1195848172490726618,4270990748507503775,16255595839858620871],[13929797090747600927,18249631648327340496,10875477221803111587,1468450494903998199],[835417896804965639,2498898613684083212,8165852132002159895,2722853811520168523],[11423663509112651104,6711766217407158980,2539149727496825258,2010797710540796124],[3340531234028882146,8925796306387549739,5501476009373288098,13246903236001786247],[11371399056580196964,15458895380291205299,6201775708463851451,16238310366257594572],[3342235367283834687,1127332824272833641,14376711136226342069,8574610696644034800],[5300499943551465971,11831769970746694728,912031620682087421,6627117083989235143],[8907158142628015780,18307402681528423259,2557221264063444330,4241224836391108208],[10900031134824932617,7382905296851846391,9082488965013738495,9412118408680486224],[1674495143464239876,13852186387078213293,11577701825659761535,14219883673528629623]];
+// Only the current skip mask (lowest four bits of GameState.skipped) is hashed
+#[rustfmt::skip]
+pub const SKIPPED_HASH: [u64; 16] = [7456279180403685119,11617032008378201292,5104425426538385547,16209996321867770858,639396018920666530,8180741491249901561,10594550179317832450,5771362927044024207,5461797585202910689,1417631271791361475,16378023829123525981,3037841377154746877,979408995529616511,1914861127155094015,13213736312336363182,5426781516896099283];
+#[rustfmt::skip]
+pub const START_PIECE_TYPE_HASH: [u64; 21] = [9861417616764978731,12550822460340705399,2104910327851210667,11111894094343139898,11718322283947495879,11772898560190538463,17660576054203582077,17721122460868856080,9714793996973623458,12333643747539216070,14759957016146581070,12801958164178137861,18144008910583165551,4483485892741012520,8559656451175129349,8679083035335663341,11946555047328062882,4085433582172502019,8724022257496507229,16662150911071912401,5169789763266167611];
+#[rustfmt::skip]
+pub const MONOMINO_PLACED_LAST_HASH: [u64; 4] = [377568461888555781,13954012682997668555,4222838452254938074,13933075647969645076];
+// Hashed instead of START_PIECE_TYPE_HASH if each color can choose its first piece
+pub const FREE_START_PIECE_HASH: u64 = 9246952314340819580;
@@ -0,0 +1,170 @@
+use super::{Action, GameState, IllegalActionReason};
+use std::fmt::{Display, Formatter, Result};
+
+// A GameState together with the actions that lead to it, for analysis and interactive tools.
+// The history stores named variations. Each variation is a line of actions that starts at the
+// initial state. The state is always somewhere on the line of the current variation:
+// The actions before it have been done and can be undone with pop, the actions after it can be
+// redone with redo. Pushing an action that differs from the next action of the line replaces the
+// rest of the line. Use branch to keep it and continue in a new variation instead.
+
+pub const MAIN_VARIATION: &str = "main";
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum HistoryError {
+    IllegalAction(Action, IllegalActionReason), // The action and the rule it violates
+    GameOver,                                   // No actions can be pushed after the game is over
+    InvalidPly(u8),                             // Not between the initial and the current ply
+    DuplicateVariation(String),                 // A variation with this name already exists
+    UnknownVariation(String),                   // There is no variation with this name
+}
+
+impl Display for HistoryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            HistoryError::IllegalAction(action, reason) => {
+                write!(f, "Illegal action {}: {}", action, reason)
+            }
+            HistoryError::GameOver => write!(f, "The game is over"),
+            HistoryError::InvalidPly(ply) => write!(f, "Can't undo to ply {}", ply),
+            HistoryError::DuplicateVariation(name) => {
+                write!(f, "The variation \"{}\" already exists", name)
+            }
+            HistoryError::UnknownVariation(name) => write!(f, "Unknown variation \"{}\"", name),
+        }
+    }
+}
+
+impl std::error::Error for HistoryError {}
+
+#[derive(Debug, Clone)]
+pub struct GameHistory {
+    state: GameState,
+    initial_ply: u8, // Ply of the state that all variations start from
+    variations: Vec<(String, Vec<Action>)>, // Name and line of each variation
+    current: usize,  // Index of the current variation
+}
+
+impl GameHistory {
+    pub fn new(state: GameState) -> Self {
+        Self {
+            initial_ply: state.ply,
+            state,
+            variations: vec![(MAIN_VARIATION.to_string(), Vec::new())],
+            current: 0,
+        }
+    }
+
+    #[inline(always)]
+    pub fn state(&self) -> &GameState {
+        &self.state
+    }
+
+    pub fn actions(&self) -> &[Action] {
+        // The actions that lead from the initial state to the current state
+        &self.line()[..self.position()]
+    }
+
+    pub fn push(&mut self, action: Action) -> std::result::Result<(), HistoryError> {
+        if self.state.is_game_over() {
+            return Err(HistoryError::GameOver);
+        }
+        if let Err(reason) = self.state.check_action(&action) {
+            return Err(HistoryError::IllegalAction(action, reason));
+        }
+        let position = self.position();
+        let line = &mut self.variations[self.current].1;
+        if line.get(position) != Some(&action) {
+            line.truncate(position);
+            line.push(action);
+        }
+        self.state.do_action(action);
+        Ok(())
+    }
+
+    pub fn pop(&mut self) -> Option<Action> {
+        // Undoes the last action. It stays in the line and can be redone.
+        let position = self.position();
+        if position == 0 {
+            return None;
+        }
+        let action = self.line()[position - 1];
+        self.state.undo_action(action);
+        Some(action)
+    }
+
+    pub fn redo(&mut self) -> Option<Action> {
+        // Does the next action of the current variation again
+        let action = *self.line().get(self.position())?;
+        self.state.do_action(action);
+        Some(action)
+    }
+
+    pub fn undo_to(&mut self, ply: u8) -> std::result::Result<(), HistoryError> {
+        if ply < self.initial_ply || ply > self.state.ply {
+            return Err(HistoryError::InvalidPly(ply));
+        }
+        while self.state.ply > ply {
+            self.pop();
+        }
+        Ok(())
+    }
+
+    pub fn branch(&mut self, name: &str) -> std::result::Result<(), HistoryError> {
+        // Creates a variation that contains the actions up to the current state and switches to it.
+        // The rest of the line stays in the previous variation.
+        if self.find_variation(name).is_some() {
+            return Err(HistoryError::DuplicateVariation(name.to_string()));
+        }
+        let line = self.actions().to_vec();
+        self.variations.push((name.to_string(), line));
+        self.current = self.variations.len() - 1;
+        Ok(())
+    }
+
+    pub fn switch_variation(&mut self, name: &str) -> std::result::Result<(), HistoryError> {
+        // Goes back to the last state that both variations have in common.
+        // The actions of the other variation can then be redone from there.
+        let index = self
+            .find_variation(name)
+            .ok_or_else(|| HistoryError::UnknownVariation(name.to_string()))?;
+        let common = self
+            .actions()
+            .iter()
+            .zip(self.variations[index].1.iter())
+            .take_while(|(a, b)| a == b)
+            .count();
+        while self.position() > common {
+            self.pop();
+        }
+        self.current = index;
+        Ok(())
+    }
+
+    pub fn current_variation(&self) -> &str {
+        &self.variations[self.current].0
+    }
+
+    pub fn variation(&self, name: &str) -> Option<&[Action]> {
+        self.find_variation(name)
+            .map(|index| &self.variations[index].1[..])
+    }
+
+    pub fn variation_names(&self) -> impl Iterator<Item = &str> {
+        self.variations.iter().map(|(name, _)| name.as_str())
+    }
+
+    #[inline(always)]
+    fn line(&self) -> &[Action] {
+        &self.variations[self.current].1
+    }
+
+    #[inline(always)]
+    fn position(&self) -> usize {
+        (self.state.ply - self.initial_ply) as usize
+    }
+
+    fn find_variation(&self, name: &str) -> Option<usize> {
+        self.variations.iter().position(|(other, _)| other == name)
+    }
+}
@@ -3,6 +3,7 @@ use std::fmt::{Display, Formatter, Result};
 
 #[repr(u8)]
 #[derive(Copy, Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
 pub enum PieceType {
     Monomino = 0,
     Domino = 1,
@@ -29,7 +30,10 @@ pub enum PieceType {
 
 impl PieceType {
     pub fn random_pentomino() -> Self {
-        let mut rng = SmallRng::from_entropy();
+        Self::random_pentomino_with_rng(&mut SmallRng::from_entropy())
+    }
+
+    pub fn random_pentomino_with_rng(rng: &mut SmallRng) -> Self {
         START_PIECE_TYPES[rng.next_u64() as usize % 11]
     }
 
@@ -38,6 +42,13 @@ impl PieceType {
         FROM_SHAPE[shape]
     }
 
+    pub fn from_xml_name(name: &str) -> Option<Self> {
+        NAMES
+            .iter()
+            .position(|names| names.1 == name)
+            .map(|piece_type| PIECE_TYPES[piece_type])
+    }
+
     pub fn to_xml_name(&self) -> String {
         NAMES[*self as usize].1.to_string()
     }
@@ -46,6 +57,13 @@ impl PieceType {
         NAMES[*self as usize].2.to_string()
     }
 
+    pub fn from_short_name(name: &str) -> Option<Self> {
+        NAMES
+            .iter()
+            .position(|names| names.2 == name)
+            .map(|piece_type| PIECE_TYPES[piece_type])
+    }
+
     pub fn piece_size(&self) -> u8 {
         match self {
             PieceType::Monomino => 1,
@@ -64,7 +82,7 @@ impl PieceType {
 
 impl Display for PieceType {
     fn fmt(&self, f: &mut Formatter<'_>) -> Result {
-        write!(f, "{}", NAMES[*self as usize].0.to_string())
+        write!(f, "{}", NAMES[*self as usize].0)
     }
 }
 
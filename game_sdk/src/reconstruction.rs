@@ -0,0 +1,153 @@
+use super::{Action, ActionList, Bitboard, GameState, PieceType, VALID_FIELDS};
+use std::collections::HashSet;
+use std::fmt::{Display, Formatter, Result};
+
+// Reconstructs an order of legal actions that leads to a given position.
+// Only the boards of the four colors and the start piece type have to be known.
+//
+// The pieces of a color can be determined from its board because pieces of the same color never share an edge.
+// The placement rules only depend on the pieces of the own color, so every color can place its pieces
+// in any order in which each piece touches a corner of a previously placed one.
+// The only interaction between the colors are skips: a color that placed all of its pieces must skip in
+// the following rounds, which is only legal if it has no possible actions at that point.
+// The search tries the pieces of each color in all orders and remembers the states that can't be completed.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ReconstructionError {
+    OverlappingColors(usize, usize),  // Two colors occupy the same fields
+    FieldOutsideBoard(usize),         // A color occupies fields that are not on the board
+    InvalidPiece(usize),              // The fields of a color can't be split into valid pieces
+    DuplicatePiece(usize, PieceType), // A color has placed the same piece type twice
+    NoSolution,                       // No order of legal actions leads to the position
+}
+
+impl Display for ReconstructionError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            ReconstructionError::OverlappingColors(a, b) => {
+                write!(f, "Colors {} and {} occupy the same fields", a, b)
+            }
+            ReconstructionError::FieldOutsideBoard(color) => {
+                write!(f, "Color {} occupies fields outside of the board", color)
+            }
+            ReconstructionError::InvalidPiece(color) => {
+                write!(f, "The fields of color {} don't form valid pieces", color)
+            }
+            ReconstructionError::DuplicatePiece(color, piece_type) => {
+                write!(f, "Color {} has placed {} twice", color, piece_type)
+            }
+            ReconstructionError::NoSolution => {
+                write!(
+                    f,
+                    "There is no legal order of actions that leads to the position"
+                )
+            }
+        }
+    }
+}
+
+impl std::error::Error for ReconstructionError {}
+
+pub fn reconstruct_actions(
+    board: [Bitboard; 4],
+    start_piece_type: PieceType,
+) -> std::result::Result<Vec<Action>, ReconstructionError> {
+    // Returns the actions (including skips) that lead from an empty board to the position.
+    // The actions end directly after the last piece has been placed.
+    let mut pieces: [Vec<Action>; 4] = [Vec::new(), Vec::new(), Vec::new(), Vec::new()];
+    for color in 0..4 {
+        if board[color] & VALID_FIELDS != board[color] {
+            return Err(ReconstructionError::FieldOutsideBoard(color));
+        }
+        for other_color in color + 1..4 {
+            if (board[color] & board[other_color]).not_empty() {
+                return Err(ReconstructionError::OverlappingColors(color, other_color));
+            }
+        }
+        for piece in board[color].get_pieces().into_iter() {
+            if piece.is_skip() {
+                return Err(ReconstructionError::InvalidPiece(color));
+            }
+            let piece_type = PieceType::from_shape(piece.get_shape() as usize);
+            if pieces[color]
+                .iter()
+                .any(|other| PieceType::from_shape(other.get_shape() as usize) == piece_type)
+            {
+                return Err(ReconstructionError::DuplicatePiece(color, piece_type));
+            }
+            pieces[color].push(piece);
+        }
+    }
+    let mut search = Search {
+        state: GameState::with_start_piece_type(start_piece_type),
+        pieces,
+        actions: Vec::new(),
+        failed: HashSet::new(),
+        al: ActionList::default(),
+    };
+    if search.search() {
+        Ok(search.actions)
+    } else {
+        Err(ReconstructionError::NoSolution)
+    }
+}
+
+struct Search {
+    state: GameState,
+    pieces: [Vec<Action>; 4], // Pieces of each color that haven't been placed yet
+    actions: Vec<Action>,
+    failed: HashSet<u64>, // Hashes of states from which the position can't be reached
+    al: ActionList,
+}
+
+impl Search {
+    fn search(&mut self) -> bool {
+        if self.pieces.iter().all(|pieces| pieces.is_empty()) {
+            return true;
+        }
+        if self.state.is_game_over() || self.failed.contains(&self.state.hash) {
+            return false;
+        }
+        let color = self.state.get_current_color();
+        let found = if self.pieces[color].is_empty() {
+            // The color has to skip, which is only legal if it has no possible actions
+            self.state.get_possible_actions(&mut self.al);
+            self.al[0].is_skip() && self.try_action(Action::SKIP)
+        } else if self.state.has_color_skipped(color) {
+            // The remaining pieces can't be placed after a skip
+            false
+        } else {
+            let mut found = false;
+            for i in 0..self.pieces[color].len() {
+                let piece = self.pieces[color][i];
+                if self.state.check_action(&piece).is_err() {
+                    continue;
+                }
+                self.pieces[color].swap_remove(i);
+                found = self.try_action(piece);
+                self.pieces[color].push(piece);
+                let last = self.pieces[color].len() - 1;
+                self.pieces[color].swap(i, last);
+                if found {
+                    break;
+                }
+            }
+            found
+        };
+        if !found {
+            self.failed.insert(self.state.hash);
+        }
+        found
+    }
+
+    fn try_action(&mut self, action: Action) -> bool {
+        self.state.do_action(action);
+        self.actions.push(action);
+        if self.search() {
+            return true;
+        }
+        self.actions.pop();
+        self.state.undo_action(action);
+        false
+    }
+}
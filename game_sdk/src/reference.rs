@@ -0,0 +1,124 @@
+use super::bitboard::PIECE_SHAPES;
+use super::{Action, ActionList, Bitboard, GameState, PieceType};
+use rand::{rngs::SmallRng, RngCore, SeedableRng};
+
+// A slow but simple move generator that is used to cross-check the bitboard move generators.
+// It checks every rule for each field of each shape at each destination using coordinates only.
+
+const CORNERS: [(i16, i16); 4] = [(0, 0), (19, 0), (19, 19), (0, 19)];
+
+fn shape_fields(shape: usize) -> Vec<(i16, i16)> {
+    // Returns the (x, y) offsets of the fields of a shape relative to its top left corner
+    let mut fields: Vec<(i16, i16)> = Vec::new();
+    for bit in 0..128 {
+        if PIECE_SHAPES[shape] & 1 << bit != 0 {
+            fields.push((bit % 21, bit / 21));
+        }
+    }
+    fields
+}
+
+fn is_occupied_by(board: &Bitboard, x: i16, y: i16) -> bool {
+    (0..20).contains(&x) && (0..20).contains(&y) && board.check_bit((x + y * 21) as u16)
+}
+
+type Grid = [[bool; 20]; 20];
+
+fn placement_grids(state: &GameState, color: usize) -> (Grid, Grid) {
+    // Returns the fields that can be covered by the color and the fields at which a piece touches a corner
+    let next_turn = state.ply as usize + (color + 4 - state.get_current_color()) % 4;
+    let own_fields = &state.board[color];
+    let mut free: Grid = [[false; 20]; 20];
+    let mut corner: Grid = [[false; 20]; 20];
+    for y in 0..20 {
+        for x in 0..20 {
+            free[y as usize][x as usize] =
+                !state.board.iter().any(|board| is_occupied_by(board, x, y))
+                    && ![(1, 0), (-1, 0), (0, 1), (0, -1)]
+                        .iter()
+                        .any(|(dx, dy)| is_occupied_by(own_fields, x + dx, y + dy));
+            // In the first round each color has to cover a corner of the board
+            corner[y as usize][x as usize] = if next_turn < 4 {
+                CORNERS.contains(&(x, y))
+            } else {
+                [(1, 1), (-1, 1), (1, -1), (-1, -1)]
+                    .iter()
+                    .any(|(dx, dy)| is_occupied_by(own_fields, x + dx, y + dy))
+            };
+        }
+    }
+    (free, corner)
+}
+
+fn destinations(free: &Grid, corner: &Grid, shape: usize) -> Bitboard {
+    let fields = shape_fields(shape);
+    let mut destinations = Bitboard::empty();
+    for y in 0..20 {
+        for x in 0..20 {
+            let is_on_board = |(dx, dy): &(i16, i16)| x + dx < 20 && y + dy < 20;
+            if fields.iter().all(is_on_board)
+                && fields
+                    .iter()
+                    .all(|(dx, dy)| free[(y + dy) as usize][(x + dx) as usize])
+                && fields
+                    .iter()
+                    .any(|(dx, dy)| corner[(y + dy) as usize][(x + dx) as usize])
+            {
+                destinations.flip_bit((x + y * 21) as u16);
+            }
+        }
+    }
+    destinations
+}
+
+pub fn shape_destinations(state: &GameState, color: usize) -> Vec<Bitboard> {
+    // Returns all destinations at which each of the 91 shapes can be placed by the color.
+    // Only the placement rules are checked, the piece types that are left and the start piece type are ignored.
+    let (free, corner) = placement_grids(state, color);
+    (0..PIECE_SHAPES.len())
+        .map(|shape| destinations(&free, &corner, shape))
+        .collect()
+}
+
+pub fn get_possible_actions(state: &GameState, al: &mut ActionList) {
+    get_possible_actions_for_color(state, state.get_current_color(), al);
+}
+
+pub fn get_possible_actions_for_color(state: &GameState, color: usize, al: &mut ActionList) {
+    // Same result as GameState::get_possible_actions_for_color, except for the order of the actions
+    let next_turn = state.ply as usize + (color + 4 - state.get_current_color()) % 4;
+    al.clear();
+    if !state.has_color_skipped(color) {
+        for (shape, destinations) in shape_destinations(state, color).into_iter().enumerate() {
+            let piece_type = PieceType::from_shape(shape);
+            if state.pieces_left[piece_type as usize][color]
+                && (next_turn > 3
+                    || !state.rules.requires_start_piece()
+                    || piece_type == state.start_piece_type)
+            {
+                al.append(destinations, shape as u16);
+            }
+        }
+    }
+    if al.size == 0 {
+        al.push(Action::SKIP);
+    }
+}
+
+pub fn seeded_positions(seed: u64, games: usize) -> Vec<GameState> {
+    // Plays random games and returns every position that occurred in them
+    let mut rng = SmallRng::seed_from_u64(seed);
+    let mut al = ActionList::default();
+    let mut positions: Vec<GameState> = Vec::new();
+    for _ in 0..games {
+        let start_piece_type = PieceType::random_pentomino_with_rng(&mut rng);
+        let mut state = GameState::with_start_piece_type(start_piece_type);
+        while !state.is_game_over() {
+            positions.push(state.clone());
+            state.get_possible_actions(&mut al);
+            state.do_action(al[rng.next_u64() as usize % al.size]);
+        }
+        positions.push(state);
+    }
+    positions
+}
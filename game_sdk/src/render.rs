@@ -0,0 +1,184 @@
+use super::gamestate::COLOR_LETTERS;
+use super::{Action, Bitboard, Field, GameState};
+
+// Renderers for logs, game reports and analysis documents.
+// Unlike display_board, they only use characters that have the same width in every terminal.
+
+// Fill colors of Blue, Yellow, Red and Green (indexed by color)
+pub const SVG_COLORS: [&str; 4] = ["#2b6cd4", "#e8c32a", "#d8403c", "#3a9d48"];
+
+pub struct SvgOptions {
+    pub field_size: u32,     // Width and height of a field in pixels
+    pub coordinates: bool,   // Draw the row and column numbers around the board
+    pub last_action: Action, // Marked with a thick outline (SKIP to disable)
+    pub heatmap: Vec<f32>, // Values between 0 and 1 that are drawn on top of the board (indexed by field)
+}
+
+impl Default for SvgOptions {
+    fn default() -> Self {
+        Self {
+            field_size: 24,
+            coordinates: true,
+            last_action: Action::SKIP,
+            heatmap: Vec::new(),
+        }
+    }
+}
+
+impl GameState {
+    pub fn to_ascii(&self, highlight: Bitboard) -> String {
+        // Empty fields are shown as '.' and occupied fields by the letter of their color.
+        // Highlighted fields are shown as '*' if they are empty and in lowercase otherwise.
+        let mut string = String::from("   ");
+        for x in 0..20 {
+            string.push_str(&format!("{:>3}", x));
+        }
+        for y in 0..20 {
+            string.push_str(&format!("\n{:>3}", y));
+            for x in 0..20 {
+                let field = x + y * 21;
+                let color = (0..4).find(|color| self.board[*color].check_bit(field));
+                let c = match (color, highlight.check_bit(field)) {
+                    (Some(color), false) => COLOR_LETTERS[color],
+                    (Some(color), true) => COLOR_LETTERS[color].to_ascii_lowercase(),
+                    (None, false) => '.',
+                    (None, true) => '*',
+                };
+                string.push_str("  ");
+                string.push(c);
+            }
+        }
+        string
+    }
+
+    pub fn to_svg(&self, options: &SvgOptions) -> String {
+        let size = options.field_size;
+        let margin = if options.coordinates { size } else { 0 };
+        let width = size * 20 + margin;
+        let mut svg = format!(
+            "<svg xmlns=\"http://www.w3.org/2000/svg\" width=\"{0}\" height=\"{0}\" viewBox=\"0 0 {0} {0}\">\n",
+            width
+        );
+        svg.push_str(&format!(
+            "<rect x=\"{0}\" y=\"{0}\" width=\"{1}\" height=\"{1}\" fill=\"#f4f4f4\"/>\n",
+            margin,
+            size * 20
+        ));
+        if options.coordinates {
+            for i in 0..20 {
+                let center = margin + i * size + size / 2;
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    center, size / 2, size / 2, i
+                ));
+                svg.push_str(&format!(
+                    "<text x=\"{}\" y=\"{}\" font-size=\"{}\" text-anchor=\"middle\" dominant-baseline=\"central\">{}</text>\n",
+                    size / 2, center, size / 2, i
+                ));
+            }
+        }
+        // Grid of empty fields
+        for y in 0..20 {
+            for x in 0..20 {
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"none\" stroke=\"#d0d0d0\"/>\n",
+                    margin + x * size,
+                    margin + y * size,
+                    size,
+                    size
+                ));
+            }
+        }
+        // Pieces of each color with an outline around each piece
+        for (color, board) in self.board.iter().enumerate() {
+            for piece in board.get_pieces().iter() {
+                if piece.is_set() {
+                    svg.push_str(&svg_piece(
+                        piece_board(*piece),
+                        size,
+                        margin,
+                        SVG_COLORS[color],
+                        "#202020",
+                        1,
+                    ));
+                }
+            }
+        }
+        for (index, value) in options.heatmap.iter().enumerate() {
+            let field = match Field::from_index(index as u16) {
+                Some(field) if *value > 0. => field,
+                _ => continue,
+            };
+            let (x, y) = (field.x() as u32, field.y() as u32);
+            svg.push_str(&format!(
+                "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"#ff6a00\" fill-opacity=\"{:.3}\"/>\n",
+                margin + x * size,
+                margin + y * size,
+                size,
+                size,
+                value.min(1.) * 0.8
+            ));
+        }
+        if options.last_action.is_set() {
+            svg.push_str(&svg_piece(
+                piece_board(options.last_action),
+                size,
+                margin,
+                "none",
+                "#000000",
+                4,
+            ));
+        }
+        svg.push_str("</svg>\n");
+        svg
+    }
+}
+
+fn piece_board(action: Action) -> Bitboard {
+    Bitboard::with_piece(action.get_destination(), action.get_shape() as usize)
+}
+
+fn svg_piece(
+    piece: Bitboard,
+    size: u32,
+    margin: u32,
+    fill: &str,
+    stroke: &str,
+    stroke_width: u32,
+) -> String {
+    // Fills all fields of the piece and draws its outline
+    let mut svg = String::new();
+    let mut path = String::new();
+    for y in 0..20 {
+        for x in 0..20 {
+            if !piece.check_bit(x + y * 21) {
+                continue;
+            }
+            let (left, top) = (margin + x as u32 * size, margin + y as u32 * size);
+            if fill != "none" {
+                svg.push_str(&format!(
+                    "<rect x=\"{}\" y=\"{}\" width=\"{}\" height=\"{}\" fill=\"{}\"/>\n",
+                    left, top, size, size, fill
+                ));
+            }
+            // Only the edges between the piece and other fields are part of the outline
+            if y == 0 || !piece.check_bit(x + (y - 1) * 21) {
+                path.push_str(&format!("M{} {}h{}", left, top, size));
+            }
+            if y == 19 || !piece.check_bit(x + (y + 1) * 21) {
+                path.push_str(&format!("M{} {}h{}", left, top + size, size));
+            }
+            if x == 0 || !piece.check_bit(x - 1 + y * 21) {
+                path.push_str(&format!("M{} {}v{}", left, top, size));
+            }
+            if x == 19 || !piece.check_bit(x + 1 + y * 21) {
+                path.push_str(&format!("M{} {}v{}", left + size, top, size));
+            }
+        }
+    }
+    svg.push_str(&format!(
+        "<path d=\"{}\" fill=\"none\" stroke=\"{}\" stroke-width=\"{}\" stroke-linecap=\"square\"/>\n",
+        path, stroke, stroke_width
+    ));
+    svg
+}
@@ -0,0 +1,98 @@
+use super::PieceType;
+use std::fmt::{Display, Formatter, Result};
+
+// Rule variations of the game.
+// The Software-Challenge selects a random pentomino that every color has to place in the first
+// round. In the official rules of Blokus each color can choose its first piece freely.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum StartPiecePolicy {
+    Random, // Every color has to place a randomly selected pentomino first (Software-Challenge)
+    Fixed(PieceType), // Every color has to place this piece type first
+    FreeChoice, // Each color can place any piece first (official rules)
+}
+
+impl StartPiecePolicy {
+    pub fn from_name(name: &str) -> Option<Self> {
+        // "random", "free" or the short name of the piece type of a fixed start piece
+        match name {
+            "random" => Some(StartPiecePolicy::Random),
+            "free" => Some(StartPiecePolicy::FreeChoice),
+            _ => PieceType::from_short_name(name).map(StartPiecePolicy::Fixed),
+        }
+    }
+
+    pub(crate) fn kind_name(self) -> &'static str {
+        // Used in the board notation and game records, which store the start piece type separately
+        match self {
+            StartPiecePolicy::Random => "random",
+            StartPiecePolicy::Fixed(_) => "fixed",
+            StartPiecePolicy::FreeChoice => "free",
+        }
+    }
+
+    pub(crate) fn from_kind_name(name: &str, start_piece_type: PieceType) -> Option<Self> {
+        match name {
+            "random" => Some(StartPiecePolicy::Random),
+            "fixed" => Some(StartPiecePolicy::Fixed(start_piece_type)),
+            "free" => Some(StartPiecePolicy::FreeChoice),
+            _ => None,
+        }
+    }
+
+    pub(crate) fn to_code(self) -> u8 {
+        // Used in the fen and the binary encoding. Fixed stores its piece type as the start piece type.
+        match self {
+            StartPiecePolicy::Random => 0,
+            StartPiecePolicy::Fixed(_) => 1,
+            StartPiecePolicy::FreeChoice => 2,
+        }
+    }
+
+    pub(crate) fn from_code(code: u8, start_piece_type: PieceType) -> Option<Self> {
+        match code {
+            0 => Some(StartPiecePolicy::Random),
+            1 => Some(StartPiecePolicy::Fixed(start_piece_type)),
+            2 => Some(StartPiecePolicy::FreeChoice),
+            _ => None,
+        }
+    }
+}
+
+impl Display for StartPiecePolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            StartPiecePolicy::Random => write!(f, "random"),
+            StartPiecePolicy::Fixed(piece_type) => write!(f, "{}", piece_type.to_short_name()),
+            StartPiecePolicy::FreeChoice => write!(f, "free"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Rules {
+    pub start_piece_policy: StartPiecePolicy,
+}
+
+impl Rules {
+    pub const SOFTWARE_CHALLENGE: Rules = Rules {
+        start_piece_policy: StartPiecePolicy::Random,
+    };
+    pub const OFFICIAL: Rules = Rules {
+        start_piece_policy: StartPiecePolicy::FreeChoice,
+    };
+
+    #[inline(always)]
+    pub fn requires_start_piece(&self) -> bool {
+        // Whether the first piece of each color has to be GameState.start_piece_type
+        self.start_piece_policy != StartPiecePolicy::FreeChoice
+    }
+}
+
+impl Default for Rules {
+    fn default() -> Self {
+        Rules::SOFTWARE_CHALLENGE
+    }
+}
@@ -0,0 +1,96 @@
+use super::GameState;
+use std::cmp::Ordering;
+use std::fmt::{Display, Formatter, Result};
+
+// Final scoring of the game: Each placed field is worth one point. A color that has placed all of
+// its pieces (89 fields) gets a bonus of 15 points and another 5 points if its final piece was the
+// monomino. The score of a team is the sum of the scores of its two colors.
+
+pub const ALL_PLACED_BONUS: i16 = 15;
+pub const MONOMINO_LAST_BONUS: i16 = 5;
+
+const ALL_FIELDS: i16 = 89; // Number of fields of all 21 pieces
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Winner {
+    TeamOne, // Blue and Red
+    TeamTwo, // Yellow and Green
+    Draw,
+}
+
+impl Display for Winner {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            Winner::TeamOne => write!(f, "Team One (BLUE, RED)"),
+            Winner::TeamTwo => write!(f, "Team Two (YELLOW, GREEN)"),
+            Winner::Draw => write!(f, "Draw"),
+        }
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ColorScore {
+    pub cells: i16,            // Number of fields covered by the pieces of the color
+    pub all_placed_bonus: i16, // ALL_PLACED_BONUS if the color placed all pieces, otherwise 0
+    pub monomino_bonus: i16,   // MONOMINO_LAST_BONUS if the monomino was placed last, otherwise 0
+}
+
+impl ColorScore {
+    #[inline(always)]
+    pub fn all_placed(&self) -> bool {
+        self.all_placed_bonus != 0
+    }
+
+    #[inline(always)]
+    pub fn total(&self) -> i16 {
+        self.cells + self.all_placed_bonus + self.monomino_bonus
+    }
+}
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub struct ScoreReport {
+    pub colors: [ColorScore; 4], // Indexed by color
+    pub teams: [i16; 2],         // Totals of team one (Blue/Red) and team two (Yellow/Green)
+    pub winner: Winner,
+}
+
+impl ScoreReport {
+    #[inline(always)]
+    pub fn difference(&self) -> i16 {
+        // Positive if team one is ahead
+        self.teams[0] - self.teams[1]
+    }
+}
+
+impl GameState {
+    #[inline(always)]
+    pub fn scores(&self) -> ScoreReport {
+        // The winner is only final when the game is over
+        let mut colors = [ColorScore {
+            cells: 0,
+            all_placed_bonus: 0,
+            monomino_bonus: 0,
+        }; 4];
+        let mut teams: [i16; 2] = [0, 0];
+        for (color, score) in colors.iter_mut().enumerate() {
+            score.cells = self.board[color].count_ones() as i16;
+            if score.cells == ALL_FIELDS {
+                score.all_placed_bonus = ALL_PLACED_BONUS;
+                if self.monomino_placed_last[color] {
+                    score.monomino_bonus = MONOMINO_LAST_BONUS;
+                }
+            }
+            teams[color & 0b1] += score.total();
+        }
+        let winner = match teams[0].cmp(&teams[1]) {
+            Ordering::Greater => Winner::TeamOne,
+            Ordering::Less => Winner::TeamTwo,
+            Ordering::Equal => Winner::Draw,
+        };
+        ScoreReport {
+            colors,
+            teams,
+            winner,
+        }
+    }
+}
@@ -0,0 +1,196 @@
+use super::{FenError, Field, GameState, StartPiecePolicy, PIECE_TYPES};
+use std::fmt::{Display, Formatter, Result};
+
+// Compact fixed-size binary encoding of a GameState for dataset files and saved games.
+// The fen needs about 400 bytes per position, the binary encoding only 222 bytes:
+//     byte 0        version of the encoding (BINARY_VERSION)
+//     byte 1        ply
+//     byte 2        start piece type (bits 0..5) and start piece policy (bits 5..7)
+//     bytes 3..11   skip history (u64, little endian)
+//     bytes 11..22  placed pieces (bit piece_type + color * 21) and monomino_placed_last (bits 84..87)
+//     bytes 22..222 the boards of the four colors, 400 bits each (bit x + y * 20)
+// All integers are little endian, so the encoding doesn't depend on the platform.
+// Decoding validates the state in the same way as try_from_fen.
+
+pub const BINARY_VERSION: u8 = 1;
+pub const BINARY_STATE_SIZE: usize = 222;
+
+const SKIPPED_OFFSET: usize = 3;
+const PIECES_OFFSET: usize = 11;
+const BOARDS_OFFSET: usize = 22;
+const BOARD_SIZE: usize = 50; // Bytes per color
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BinaryError {
+    InvalidLength(usize),   // Number of bytes found
+    UnsupportedVersion(u8), // The version of the encoding is unknown
+    InvalidState(FenError), // The decoded state is not valid
+}
+
+impl Display for BinaryError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            BinaryError::InvalidLength(length) => {
+                write!(f, "Expected {} bytes, found {}", BINARY_STATE_SIZE, length)
+            }
+            BinaryError::UnsupportedVersion(version) => write!(
+                f,
+                "Unsupported version {} (expected {})",
+                version, BINARY_VERSION
+            ),
+            BinaryError::InvalidState(error) => write!(f, "Invalid state: {}", error),
+        }
+    }
+}
+
+impl std::error::Error for BinaryError {}
+
+impl GameState {
+    pub fn to_bytes(&self) -> [u8; BINARY_STATE_SIZE] {
+        let mut bytes = [0; BINARY_STATE_SIZE];
+        bytes[0] = BINARY_VERSION;
+        bytes[1] = self.ply;
+        bytes[2] = self.start_piece_type as u8 | self.rules.start_piece_policy.to_code() << 5;
+        bytes[SKIPPED_OFFSET..PIECES_OFFSET].copy_from_slice(&self.skipped.to_le_bytes());
+        let mut pieces: u128 = 0;
+        for color in 0..4 {
+            for piece_type in 0..21 {
+                if !self.pieces_left[piece_type][color] {
+                    pieces |= 1 << (piece_type + color * 21);
+                }
+            }
+            pieces |= (self.monomino_placed_last[color] as u128) << (84 + color);
+        }
+        bytes[PIECES_OFFSET..BOARDS_OFFSET].copy_from_slice(&pieces.to_le_bytes()[..11]);
+        for (color, board) in self.board.iter().enumerate() {
+            for field in board.fields() {
+                let bit = (field.x() + field.y() * 20) as usize;
+                bytes[BOARDS_OFFSET + color * BOARD_SIZE + bit / 8] |= 1 << (bit % 8);
+            }
+        }
+        bytes
+    }
+
+    pub fn try_from_bytes(bytes: &[u8]) -> std::result::Result<GameState, BinaryError> {
+        if bytes.len() != BINARY_STATE_SIZE {
+            return Err(BinaryError::InvalidLength(bytes.len()));
+        }
+        if bytes[0] != BINARY_VERSION {
+            return Err(BinaryError::UnsupportedVersion(bytes[0]));
+        }
+        let mut state = GameState {
+            ply: bytes[1],
+            ..GameState::default()
+        };
+        let start_piece_type = (bytes[2] & 0b11111) as usize;
+        if start_piece_type >= PIECE_TYPES.len() {
+            return Err(BinaryError::InvalidState(FenError::InvalidStartPieceType(
+                start_piece_type,
+            )));
+        }
+        state.start_piece_type = PIECE_TYPES[start_piece_type];
+        let policy = bytes[2] >> 5;
+        state.rules.start_piece_policy =
+            StartPiecePolicy::from_code(policy, state.start_piece_type)
+                .ok_or(BinaryError::InvalidState(FenError::InvalidRules(policy)))?;
+        let mut skipped = [0; 8];
+        skipped.copy_from_slice(&bytes[SKIPPED_OFFSET..PIECES_OFFSET]);
+        state.skipped = u64::from_le_bytes(skipped);
+        let mut pieces = [0; 16];
+        pieces[..11].copy_from_slice(&bytes[PIECES_OFFSET..BOARDS_OFFSET]);
+        let pieces = u128::from_le_bytes(pieces);
+        for color in 0..4 {
+            for piece_type in 0..21 {
+                state.pieces_left[piece_type][color] = pieces & 1 << (piece_type + color * 21) == 0;
+            }
+            state.monomino_placed_last[color] = pieces & 1 << (84 + color) != 0;
+        }
+        for color in 0..4 {
+            let board = &bytes[BOARDS_OFFSET + color * BOARD_SIZE..][..BOARD_SIZE];
+            state.board[color] = (0..400)
+                .filter(|bit| board[bit / 8] & 1 << (bit % 8) != 0)
+                .filter_map(|bit| Field::new(bit as u16 % 20, bit as u16 / 20))
+                .collect();
+        }
+        state.finish_loading().map_err(BinaryError::InvalidState)
+    }
+}
+
+#[cfg(feature = "serde")]
+mod serde_support {
+    use crate::{Bitboard, GameState, PieceType, Rules};
+    use serde::de::Error;
+    use serde::{Deserialize, Deserializer, Serialize, Serializer};
+
+    // Bitboards are serialized as eight u64 from the most to the least significant bits (like the
+    // u128 in the fen), because many formats don't support u128. The format doesn't depend on the
+    // backend. States without rules are read with the default rules. GameState only serializes the
+    // fields that can't be derived from the others. Deserializing a GameState validates it and
+    // computes the hash.
+
+    impl Serialize for Bitboard {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            let mut parts = [0; 8];
+            for (i, part) in self.to_u128s().iter().enumerate() {
+                parts[i * 2] = (part >> 64) as u64;
+                parts[i * 2 + 1] = *part as u64;
+            }
+            parts.serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for Bitboard {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let parts = <[u64; 8]>::deserialize(deserializer)?;
+            let mut board = [0; 4];
+            for (i, part) in board.iter_mut().enumerate() {
+                *part = (parts[i * 2] as u128) << 64 | parts[i * 2 + 1] as u128;
+            }
+            Ok(Bitboard::from_u128s(board))
+        }
+    }
+
+    #[derive(Serialize, Deserialize)]
+    struct SerializedGameState {
+        ply: u8,
+        board: [Bitboard; 4],
+        pieces_left: [[bool; 4]; 21],
+        monomino_placed_last: [bool; 4],
+        skipped: u64,
+        start_piece_type: PieceType,
+        #[serde(default)]
+        rules: Rules,
+    }
+
+    impl Serialize for GameState {
+        fn serialize<S: Serializer>(&self, serializer: S) -> Result<S::Ok, S::Error> {
+            SerializedGameState {
+                ply: self.ply,
+                board: self.board,
+                pieces_left: self.pieces_left,
+                monomino_placed_last: self.monomino_placed_last,
+                skipped: self.skipped,
+                start_piece_type: self.start_piece_type,
+                rules: self.rules,
+            }
+            .serialize(serializer)
+        }
+    }
+
+    impl<'de> Deserialize<'de> for GameState {
+        fn deserialize<D: Deserializer<'de>>(deserializer: D) -> Result<Self, D::Error> {
+            let fields = SerializedGameState::deserialize(deserializer)?;
+            let state = GameState {
+                ply: fields.ply,
+                board: fields.board,
+                pieces_left: fields.pieces_left,
+                monomino_placed_last: fields.monomino_placed_last,
+                skipped: fields.skipped,
+                start_piece_type: fields.start_piece_type,
+                rules: fields.rules,
+                ..GameState::default()
+            };
+            state.finish_loading().map_err(D::Error::custom)
+        }
+    }
+}
@@ -0,0 +1,149 @@
+use super::bitboard::PIECE_SHAPES;
+use super::{Action, Bitboard, Field, GameState};
+
+// The eight symmetries of the square board (the dihedral group of order 8).
+// Every color can start in any corner, so the transformed state is equivalent to the original one.
+// The colors are never permuted because that would change the order of play.
+
+#[derive(Copy, Clone, PartialEq, Eq, Debug)]
+pub enum Symmetry {
+    Identity,
+    RotateRight,        // Rotate by 90 degrees clockwise
+    Rotate180,          // Rotate by 180 degrees
+    RotateLeft,         // Rotate by 90 degrees counterclockwise
+    Mirror,             // Reverse the columns (x -> 19 - x)
+    Flip,               // Reverse the rows (y -> 19 - y)
+    MirrorDiagonal,     // Swap x and y
+    MirrorAntiDiagonal, // Swap x and y and rotate by 180 degrees
+}
+
+pub const SYMMETRIES: [Symmetry; 8] = [
+    Symmetry::Identity,
+    Symmetry::RotateRight,
+    Symmetry::Rotate180,
+    Symmetry::RotateLeft,
+    Symmetry::Mirror,
+    Symmetry::Flip,
+    Symmetry::MirrorDiagonal,
+    Symmetry::MirrorAntiDiagonal,
+];
+
+impl Symmetry {
+    pub fn inverse(self) -> Symmetry {
+        match self {
+            Symmetry::RotateRight => Symmetry::RotateLeft,
+            Symmetry::RotateLeft => Symmetry::RotateRight,
+            s => s, // All other symmetries are their own inverse
+        }
+    }
+
+    #[inline(always)]
+    pub fn transform_coordinates(self, x: u16, y: u16) -> (u16, u16) {
+        match self {
+            Symmetry::Identity => (x, y),
+            Symmetry::RotateRight => (19 - y, x),
+            Symmetry::Rotate180 => (19 - x, 19 - y),
+            Symmetry::RotateLeft => (y, 19 - x),
+            Symmetry::Mirror => (19 - x, y),
+            Symmetry::Flip => (x, 19 - y),
+            Symmetry::MirrorDiagonal => (y, x),
+            Symmetry::MirrorAntiDiagonal => (19 - y, 19 - x),
+        }
+    }
+
+    pub fn transform_field(self, field_index: u16) -> u16 {
+        let x = field_index % 21;
+        let y = (field_index - x) / 21;
+        let (x, y) = self.transform_coordinates(x, y);
+        x + y * 21
+    }
+
+    pub fn transform(self, field: Field) -> Field {
+        let (x, y) = self.transform_coordinates(field.x(), field.y());
+        Field::new(x, y).expect("Symmetries map the board onto itself")
+    }
+
+    pub fn transform_bitboard(self, board: Bitboard) -> Bitboard {
+        // Only works for fields inside of VALID_FIELDS
+        match self {
+            Symmetry::Identity => board,
+            Symmetry::RotateRight => board.rotate_right(),
+            Symmetry::Rotate180 => board.mirror().flip(),
+            Symmetry::RotateLeft => board.rotate_left(),
+            Symmetry::Mirror => board.mirror(),
+            Symmetry::Flip => board.flip(),
+            Symmetry::MirrorDiagonal => board.mirror_diagonal(),
+            Symmetry::MirrorAntiDiagonal => board.mirror_diagonal().mirror().flip(),
+        }
+    }
+
+    pub fn transform_action(self, action: Action) -> Action {
+        if action.is_skip() || self == Symmetry::Identity {
+            return action;
+        }
+        let destination = action.get_destination();
+        let x = destination % 21;
+        let y = (destination - x) / 21;
+        // Transform each field of the shape and find the new top left corner
+        let mut fields: [(u16, u16); 5] = [(0, 0); 5];
+        let mut n: usize = 0;
+        let mut left: u16 = 20;
+        let mut top: u16 = 20;
+        let mut shape = PIECE_SHAPES[action.get_shape() as usize];
+        while shape != 0 {
+            let bit = shape.trailing_zeros() as u16;
+            shape ^= 1 << bit;
+            let (field_x, field_y) = self.transform_coordinates(x + bit % 21, y + bit / 21);
+            left = left.min(field_x);
+            top = top.min(field_y);
+            fields[n] = (field_x, field_y);
+            n += 1;
+        }
+        let mut transformed_shape: u128 = 0;
+        for (field_x, field_y) in fields.iter().take(n) {
+            transformed_shape |= 1 << (field_x - left + (field_y - top) * 21);
+        }
+        let shape = PIECE_SHAPES
+            .iter()
+            .position(|s| *s == transformed_shape)
+            .expect("Every transformed shape is a valid shape");
+        Action::set(left + top * 21, shape as u16)
+    }
+
+    pub fn transform_state(self, state: &GameState) -> GameState {
+        // The hash is computed from scratch. Callers that only need the boards should transform them directly.
+        let mut transformed = state.clone();
+        for board in transformed.board.iter_mut() {
+            *board = self.transform_bitboard(*board);
+        }
+        transformed.hash = transformed.compute_hash();
+        transformed
+    }
+}
+
+fn state_key(state: &GameState) -> [u128; 16] {
+    let mut key: [u128; 16] = [0; 16];
+    for (color, board) in state.board.iter().enumerate() {
+        key[color * 4..color * 4 + 4].copy_from_slice(&board.to_u128s());
+    }
+    key
+}
+
+pub fn canonical_form(state: &GameState) -> (GameState, Symmetry) {
+    // Returns the smallest of the eight equivalent states and the symmetry that leads to it.
+    // Actions of the canonical state can be transformed back using the inverse symmetry.
+    let mut best = (
+        Symmetry::Identity.transform_state(state),
+        Symmetry::Identity,
+    );
+    let mut best_key = state_key(&best.0);
+    for symmetry in SYMMETRIES.iter().skip(1) {
+        let transformed = symmetry.transform_state(state);
+        let key = state_key(&transformed);
+        if key < best_key {
+            best_key = key;
+            best = (transformed, *symmetry);
+        }
+    }
+    best
+}
@@ -0,0 +1,206 @@
+use super::{Action, Bitboard, GameState, PieceType, PIECE_TYPES, START_FIELDS, VALID_FIELDS};
+use std::fmt::{Display, Formatter, Result};
+
+// A thorough check of the boards that reports every violated placement rule.
+// check_color_integrity only tells whether the boards match pieces_left. This validator also finds
+// positions that can't occur in a game, for example pieces that are not connected to a start corner.
+//
+// Every connected group of fields of a color is treated as one piece. Each polyomino with up to
+// five fields is one of the 21 piece types, so a larger group always consists of several pieces
+// of the same color that share an edge.
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum BoardViolation {
+    FieldOutsideBoard(usize, Bitboard), // Fields that are not in VALID_FIELDS
+    OverlappingColors(usize, usize, Bitboard), // Fields occupied by both colors
+    EdgeContact(usize, Bitboard),       // Pieces of the color that share an edge
+    DuplicatePiece(usize, PieceType, Bitboard), // Pieces of the same type
+    NotConnectedToStart(usize, Bitboard), // Pieces without a path to a start corner
+    MissingStartPiece(usize),           // The start piece is not in a corner
+    PiecesLeftMismatch(usize, PieceType), // pieces_left does not match the board
+}
+
+impl Display for BoardViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> Result {
+        match self {
+            BoardViolation::FieldOutsideBoard(color, fields) => write!(
+                f,
+                "Color {} occupies fields outside of the board: {}",
+                color,
+                field_list(*fields)
+            ),
+            BoardViolation::OverlappingColors(a, b, fields) => write!(
+                f,
+                "Colors {} and {} occupy the same fields: {}",
+                a,
+                b,
+                field_list(*fields)
+            ),
+            BoardViolation::EdgeContact(color, fields) => write!(
+                f,
+                "Pieces of color {} share an edge: {}",
+                color,
+                field_list(*fields)
+            ),
+            BoardViolation::DuplicatePiece(color, piece_type, fields) => write!(
+                f,
+                "Color {} has placed {} more than once: {}",
+                color,
+                piece_type,
+                field_list(*fields)
+            ),
+            BoardViolation::NotConnectedToStart(color, fields) => write!(
+                f,
+                "Pieces of color {} are not connected to a start corner: {}",
+                color,
+                field_list(*fields)
+            ),
+            BoardViolation::MissingStartPiece(color) => write!(
+                f,
+                "Color {} has placed pieces but the start piece is not in a corner",
+                color
+            ),
+            BoardViolation::PiecesLeftMismatch(color, piece_type) => write!(
+                f,
+                "The pieces left of color {} don't match its board ({})",
+                color, piece_type
+            ),
+        }
+    }
+}
+
+impl std::error::Error for BoardViolation {}
+
+fn field_list(mut fields: Bitboard) -> String {
+    // Formats the fields as a list of (x, y) coordinates
+    let mut list: Vec<String> = Vec::new();
+    while fields.not_empty() {
+        let field_index = fields.trailing_zeros();
+        fields.flip_bit(field_index);
+        list.push(format!("({}, {})", field_index % 21, field_index / 21));
+    }
+    list.join(" ")
+}
+
+fn connected_groups(mut board: Bitboard) -> Vec<Bitboard> {
+    // Splits the board into groups of fields that are connected by edges
+    let mut groups: Vec<Bitboard> = Vec::new();
+    while board.not_empty() {
+        let mut group = Bitboard::bit(board.trailing_zeros());
+        loop {
+            let grown = group | (group.neighbors() & board);
+            if grown == group {
+                break;
+            }
+            group = grown;
+        }
+        board ^= group;
+        groups.push(group);
+    }
+    groups
+}
+
+impl GameState {
+    pub fn validate_board(&self) -> Vec<BoardViolation> {
+        // Returns all violated rules. The position is valid if the list is empty.
+        let mut violations: Vec<BoardViolation> = Vec::new();
+        for color in 0..4 {
+            let outside = self.board[color] & !VALID_FIELDS;
+            if outside.not_empty() {
+                violations.push(BoardViolation::FieldOutsideBoard(color, outside));
+            }
+            for other_color in color + 1..4 {
+                let overlap = self.board[color] & self.board[other_color];
+                if overlap.not_empty() {
+                    violations.push(BoardViolation::OverlappingColors(
+                        color,
+                        other_color,
+                        overlap,
+                    ));
+                }
+            }
+        }
+        for color in 0..4 {
+            self.validate_color(color, &mut violations);
+        }
+        violations
+    }
+
+    fn validate_color(&self, color: usize, violations: &mut Vec<BoardViolation>) {
+        let groups = connected_groups(self.board[color] & VALID_FIELDS);
+        let mut placed = [Bitboard::empty(); 21];
+        let mut count = [0; 21];
+        let mut has_edge_contact = false;
+        let mut start_piece_in_corner = false;
+        for group in groups.iter() {
+            if group.count_ones() > 5 {
+                violations.push(BoardViolation::EdgeContact(color, *group));
+                has_edge_contact = true;
+                continue;
+            }
+            let piece = Action::from_bitboard(*group);
+            let piece_type = PieceType::from_shape(piece.get_shape() as usize);
+            count[piece_type as usize] += 1;
+            placed[piece_type as usize] |= *group;
+            // With free choice any piece in a corner can be the start piece
+            let is_start_piece =
+                !self.rules.requires_start_piece() || piece_type == self.start_piece_type;
+            if is_start_piece && (*group & START_FIELDS).not_empty() {
+                start_piece_in_corner = true;
+            }
+        }
+        for piece_type in PIECE_TYPES.iter() {
+            if count[*piece_type as usize] > 1 {
+                violations.push(BoardViolation::DuplicatePiece(
+                    color,
+                    *piece_type,
+                    placed[*piece_type as usize],
+                ));
+            }
+        }
+        if !groups.is_empty() && !start_piece_in_corner {
+            violations.push(BoardViolation::MissingStartPiece(color));
+        }
+        // Every piece has to be reachable from a piece in a start corner by touching corners
+        let mut connected = Bitboard::empty();
+        let mut unconnected: Vec<Bitboard> = Vec::new();
+        for group in groups.into_iter() {
+            if (group & START_FIELDS).not_empty() {
+                connected |= group;
+            } else {
+                unconnected.push(group);
+            }
+        }
+        loop {
+            let corners = connected.diagonal_neighbors();
+            let size = unconnected.len();
+            unconnected.retain(|group| {
+                if (*group & corners).not_empty() {
+                    connected |= *group;
+                    false
+                } else {
+                    true
+                }
+            });
+            if unconnected.len() == size {
+                break;
+            }
+        }
+        if !unconnected.is_empty() {
+            let fields = unconnected
+                .into_iter()
+                .fold(Bitboard::empty(), |fields, group| fields | group);
+            violations.push(BoardViolation::NotConnectedToStart(color, fields));
+        }
+        if has_edge_contact {
+            // The piece types inside of the groups with edge contact are unknown
+            return;
+        }
+        for piece_type in PIECE_TYPES.iter() {
+            let is_placed = placed[*piece_type as usize].not_empty();
+            if self.pieces_left[*piece_type as usize][color] == is_placed {
+                violations.push(BoardViolation::PiecesLeftMismatch(color, *piece_type));
+            }
+        }
+    }
+}
@@ -111,7 +111,9 @@ fn test() {
     let results: [u64; 4] = [96564378, 815135, 200870, 56253];
     for (i, fen) in TEST_FENS.iter().enumerate() {
         let mut state = GameState::from_fen(fen.to_string());
+        let hash = state.hash;
         assert_eq!(results[i], perft(&mut state, 3, &mut als));
-        assert_eq!(state.hash, 0);
+        assert_eq!(state.hash, hash);
+        assert_eq!(state.hash, state.compute_hash());
     }
 }
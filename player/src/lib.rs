@@ -9,10 +9,17 @@ pub mod simple_client {
 
     pub struct SimpleClient {
         rng: SmallRng,
+        seed: Option<u64>,
         al: ActionList,
     }
 
     impl SimpleClient {
+        pub fn with_seed(seed: u64) -> Self {
+            let mut client = Self::default();
+            client.set_seed(seed);
+            client
+        }
+
         pub fn get_action(&mut self, state: &GameState) -> Action {
             state.get_possible_actions(&mut self.al);
             self.al[self.rng.next_u64() as usize % self.al.size]
@@ -23,12 +30,25 @@ pub mod simple_client {
         fn on_move_request(&mut self, state: &GameState) -> Action {
             self.get_action(state)
         }
+
+        fn on_reset(&mut self) {
+            // Every game of a seeded client starts with the same random numbers
+            if let Some(seed) = self.seed {
+                self.rng = SmallRng::seed_from_u64(seed);
+            }
+        }
+
+        fn set_seed(&mut self, seed: u64) {
+            self.seed = Some(seed);
+            self.rng = SmallRng::seed_from_u64(seed);
+        }
     }
 
     impl Default for SimpleClient {
         fn default() -> Self {
             Self {
                 rng: SmallRng::from_entropy(),
+                seed: None,
                 al: ActionList::default(),
             }
         }
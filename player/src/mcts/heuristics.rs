@@ -1,7 +1,7 @@
 use super::float_stuff::{pow2, sqrt};
 use super::node::Node;
-use game_sdk::{Action, ActionList, Bitboard, GameState, PieceType, Player};
-use game_sdk::{START_FIELDS, VALID_FIELDS};
+use game_sdk::START_FIELDS;
+use game_sdk::{analysis, Action, ActionList, Bitboard, GameState, PieceType, Player, PIECE_TYPES};
 
 pub const SEARCH_SEEDING_VISITS: f32 = 23.; // Number of visits that each child node is initialized with
 
@@ -24,36 +24,26 @@ pub const HEURISTIC_PARAMETERS: [f32; 13] = [
 pub const BIAS: f32 = 0.049048785;
 
 fn calculate_placement_fields(state: &GameState, occupied: &Bitboard) -> [Bitboard; 4] {
-    // Calculate the corners at which each color can place new pieces
+    // Calculate the corners at which each color can place new pieces.
+    // Unlike analysis::placement_fields, every color gets the free start fields in the first round
+    // and skipped colors are not treated differently, because the parameters were tuned like this.
     let mut placement_fields: [Bitboard; 4] = [Bitboard::empty(); 4];
-    #[allow(clippy::needless_range_loop)]
-    for color in 0..4 {
-        let current_color_fields = state.board[color];
-        let other_colors_fields = *occupied & !current_color_fields;
-        let legal_fields = !(*occupied | current_color_fields.neighbors()) & VALID_FIELDS;
-        placement_fields[color] = if state.ply > 3 {
-            current_color_fields.diagonal_neighbors() & legal_fields
+    for (color, fields) in placement_fields.iter_mut().enumerate() {
+        *fields = if state.ply > 3 {
+            state.board[color].diagonal_neighbors() & state.legal_fields(color)
         } else {
-            START_FIELDS & !other_colors_fields
+            START_FIELDS & !(*occupied & !state.board[color])
         };
     }
     placement_fields
 }
 
-fn estimate_reachable_fields(
-    state: &GameState,
-    placement_fields: &[Bitboard; 4],
-    occupied: &Bitboard,
-) -> [Bitboard; 4] {
-    // Estimate the area that each color can reach
+fn estimate_reachable_fields(state: &GameState, placement_fields: &[Bitboard; 4]) -> [Bitboard; 4] {
+    // Estimate the area that each color can reach in the next rounds
     let mut reachable_fields: [Bitboard; 4] = [Bitboard::empty(); 4];
     for color in 0..4 {
-        let mut reachable = placement_fields[color];
-        let unreachable = state.board[color].neighbors() | *occupied;
-        for _ in 0..4 {
-            reachable |= reachable.neighbors() & !unreachable;
-        }
-        reachable_fields[color] = reachable;
+        reachable_fields[color] =
+            analysis::expand(placement_fields[color], state.legal_fields(color), 4);
     }
     reachable_fields
 }
@@ -77,14 +67,10 @@ fn calculate_leaks(
     leaks
 }
 
-fn get_min_distance_to_center(piece: &mut Bitboard) -> f32 {
+fn get_min_distance_to_center(piece: Bitboard) -> f32 {
     let mut min_distance_to_center = 100.;
-    while piece.not_empty() {
-        let bit = piece.trailing_zeros();
-        piece.flip_bit(bit);
-        let x = bit % 21;
-        let y = (bit - x) / 21;
-        let distance_to_center = sqrt(pow2(9.5 - x as f32) + pow2(9.5 - y as f32));
+    for field in piece.fields() {
+        let distance_to_center = sqrt(pow2(9.5 - field.x() as f32) + pow2(9.5 - field.y() as f32));
         if distance_to_center < min_distance_to_center {
             min_distance_to_center = distance_to_center;
         }
@@ -95,7 +81,7 @@ fn get_min_distance_to_center(piece: &mut Bitboard) -> f32 {
 pub fn expand_node(
     node: &mut Node,
     state: &GameState,
-    al: &mut ActionList, // Assumes that the ActionList already contains all legal actions
+    al: &mut ActionList, // Contains all legal actions, also used as a buffer in the first two rounds
 ) {
     let current_color = state.get_current_color();
     let next_opponent_color = (current_color + 1) & 0b11;
@@ -104,7 +90,7 @@ pub fn expand_node(
 
     let occupied = state.get_occupied_fields();
     let placement_fields = calculate_placement_fields(state, &occupied);
-    let reachable_fields = estimate_reachable_fields(state, &placement_fields, &occupied);
+    let reachable_fields = estimate_reachable_fields(state, &placement_fields);
     let leaks = calculate_leaks(state, &placement_fields, &reachable_fields, &occupied);
     // All placement fields of the opponent colors
     let opponent_placement_fields =
@@ -117,17 +103,11 @@ pub fn expand_node(
         & (occupied & !state.board[current_color]).neighbors()
         & !(occupied & !state.board[current_color]).diagonal_neighbors();
 
-    for i in 0..al.size {
-        let action = al[i];
+    let mut add_child = |action: Action| {
         let shape = action.get_shape() as usize;
         let destination = action.get_destination();
-        let piece_type = PieceType::from_shape(shape);
-        let piece_size = piece_type.piece_size();
-        if state.ply < 8 && piece_size < 5 {
-            // Ignore small pieces in the first two rounds
-            continue;
-        }
-        let mut piece = Bitboard::with_piece(destination, shape);
+        let piece_size = PieceType::from_shape(shape).piece_size();
+        let piece = Bitboard::with_piece(destination, shape);
         let mut heuristic_value = piece_size as f32 * HEURISTIC_PARAMETERS[0];
         // Evaluate leaks
         heuristic_value +=
@@ -167,7 +147,7 @@ pub fn expand_node(
             (piece & placement_fields[second_color]).count_ones() as f32 * HEURISTIC_PARAMETERS[10];
         heuristic_value += (piece & k).count_ones() as f32 * HEURISTIC_PARAMETERS[11];
         if state.ply < 8 {
-            heuristic_value += get_min_distance_to_center(&mut piece) * HEURISTIC_PARAMETERS[12];
+            heuristic_value += get_min_distance_to_center(piece) * HEURISTIC_PARAMETERS[12];
         }
         node.children.push(Node {
             children: Vec::new(),
@@ -175,9 +155,23 @@ pub fn expand_node(
             n: SEARCH_SEEDING_VISITS,
             q: (heuristic_value + BIAS) * SEARCH_SEEDING_VISITS,
         })
+    };
+    if state.ply < 8 {
+        // Ignore small pieces in the first two rounds
+        for piece_type in PIECE_TYPES.iter().filter(|p| p.piece_size() == 5) {
+            state.get_possible_actions_for_piece(*piece_type, current_color, al);
+            for i in 0..al.size {
+                add_child(al[i]);
+            }
+        }
+    } else {
+        for i in 0..al.size {
+            add_child(al[i]);
+        }
     }
 }
 
+#[derive(Default)]
 pub struct HeuristicPlayer {
     al: ActionList,
 }
@@ -190,9 +184,9 @@ impl Player for HeuristicPlayer {
             return Action::SKIP;
         }
         node.children = Vec::with_capacity(self.al.size);
-        expand_node(&mut node, state, &mut self.al);
         let mut best_action = self.al[0];
-        let mut best_value = std::f32::NEG_INFINITY;
+        expand_node(&mut node, state, &mut self.al);
+        let mut best_value = f32::NEG_INFINITY;
         for child_node in node.children.iter() {
             let heuristic_value = child_node.get_value();
             if heuristic_value > best_value {
@@ -203,11 +197,3 @@ impl Player for HeuristicPlayer {
         best_action
     }
 }
-
-impl Default for HeuristicPlayer {
-    fn default() -> Self {
-        Self {
-            al: ActionList::default(),
-        }
-    }
-}
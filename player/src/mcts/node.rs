@@ -33,7 +33,7 @@ impl Node {
         if self.n > 0. {
             self.q / self.n
         } else {
-            std::f32::NEG_INFINITY
+            f32::NEG_INFINITY
         }
     }
 
@@ -50,7 +50,7 @@ impl Node {
             return if self.n > 0. {
                 self.q / self.n + c * sqrt(ln(parent_n) / self.n)
             } else {
-                std::f32::INFINITY
+                f32::INFINITY
             };
         }
         let (rave_n, rave_q) = rave_table.get_values(self.action, color);
@@ -74,7 +74,7 @@ impl Node {
         let c_adjusted = C + C_FACTOR * ln((1. + self.n + C_BASE) / C_BASE);
         let fpu_base = (self.n - self.q) / self.n - FPU_R;
         let mut best_child = 0;
-        let mut best_value = std::f32::NEG_INFINITY;
+        let mut best_value = f32::NEG_INFINITY;
         for (i, child) in self.children.iter().enumerate() {
             let value =
                 child.get_uct_value(self.n, c_adjusted, color, rave_table, fpu_base, is_root);
@@ -111,6 +111,7 @@ impl Node {
         }
     }
 
+    #[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
     pub fn iteration(
         &mut self,
         al: &mut ActionList,
@@ -165,7 +166,7 @@ impl Node {
     pub fn best_child(&mut self) -> &mut Node {
         let value = 1. - self.get_value();
         let mut best_child: usize = 0;
-        let mut best_value = std::f32::NEG_INFINITY;
+        let mut best_value = f32::NEG_INFINITY;
         for (i, child) in self.children.iter().enumerate() {
             let mut child_value = child.get_value();
             if value > 0.99 && child.action.is_set() && child.action.get_shape() == 0 {
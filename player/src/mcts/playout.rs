@@ -1,6 +1,5 @@
 use super::rave::RaveTable;
 use game_sdk::{Action, Bitboard, GameState, PieceType};
-use game_sdk::{START_FIELDS, VALID_FIELDS};
 use rand::{rngs::SmallRng, RngCore};
 
 type ShapeFunction = fn(Bitboard, Bitboard) -> Bitboard;
@@ -31,26 +30,36 @@ pub fn playout(state: &mut GameState, rng: &mut SmallRng, rave_table: &mut RaveT
     }
 }
 
+fn placement_fields(state: &GameState, color: usize) -> (Bitboard, Bitboard) {
+    // Fields that newly placed pieces can occupy
+    let legal_fields = state.legal_fields(color);
+    // The corners of existing pieces (or the start fields) at which new pieces can be placed
+    (legal_fields, state.placement_corners(color) & legal_fields)
+}
+
 pub fn random_action(state: &GameState, rng: &mut SmallRng, pentomino_only: bool) -> Action {
     let color = state.get_current_color();
     if state.has_color_skipped(color) {
         return Action::SKIP;
     }
-    // Fields that are occupied by the current color
-    let own_fields = state.board[color];
-    // All fields that are occupied by the other colors
-    let other_fields = state.get_occupied_fields() & !own_fields;
-    // Fields that newly placed pieces can occupy
-    let legal_fields = !(own_fields | other_fields | own_fields.neighbors()) & VALID_FIELDS;
-    // Calculate the corners of existing pieces at which new pieces can be placed
-    let p = if state.ply > 3 {
-        own_fields.diagonal_neighbors() & legal_fields
-    } else {
-        START_FIELDS & !other_fields
-    };
+    let (legal_fields, p) = placement_fields(state, color);
     if p.is_empty() {
         return Action::SKIP;
     }
+    if let Some(piece_type) = state.required_piece_type(color) {
+        // Only the start piece type can be placed, so try its shapes starting at a random one
+        let offset = rng.next_u32() as usize;
+        for i in 0..91 {
+            let shape = (offset + i) % 91;
+            if PieceType::from_shape(shape) == piece_type {
+                let mut destinations = SHAPE_FUNCTIONS[shape](legal_fields, p);
+                if destinations.not_empty() {
+                    return Action::set(destinations.random_field(rng), shape as u16);
+                }
+            }
+        }
+        return Action::SKIP;
+    }
     for _ in 0..MOVEGEN_RETRIES {
         // Select a random shape
         let shape = if pentomino_only {
@@ -455,3 +464,28 @@ const PENTOMINO_SHAPES: [usize; 63] = [
     52, 53, 54, 55, 56, 57, 58, 59, 60, 61, 62, 63, 64, 65, 66, 67, 68, 69, 70, 71, 72, 73, 74, 75,
     76, 77, 78, 79, 80, 81, 82, 83, 84, 85, 86, 87, 88, 89, 90,
 ];
+
+#[cfg(test)]
+mod tests {
+    use super::{placement_fields, SHAPE_FUNCTIONS};
+    use game_sdk::reference;
+
+    #[test]
+    fn test_shape_functions() {
+        // Compares the shape functions with the reference move generator
+        for state in reference::seeded_positions(2022, 25).iter() {
+            let color = state.get_current_color();
+            let (legal_fields, p) = placement_fields(state, color);
+            let destinations = reference::shape_destinations(state, color);
+            for (shape, shape_function) in SHAPE_FUNCTIONS.iter().enumerate() {
+                assert_eq!(
+                    shape_function(legal_fields, p),
+                    destinations[shape],
+                    "shape {} in {}",
+                    shape,
+                    state.to_fen()
+                );
+            }
+        }
+    }
+}
@@ -9,10 +9,18 @@ pub struct Mcts {
     root_state: GameState,
     time_limit: Option<i64>,
     iteration_limit: Option<usize>,
+    rng: SmallRng,
+    seed: Option<u64>,
     pub rave_table: RaveTable,
 }
 
 impl Mcts {
+    pub fn with_seed(seed: u64) -> Self {
+        let mut mcts = Self::default();
+        mcts.set_seed(seed);
+        mcts
+    }
+
     pub fn set_iteration_limit(&mut self, iteration_limit: usize) {
         self.time_limit = None;
         self.iteration_limit = Some(iteration_limit);
@@ -60,13 +68,13 @@ impl Mcts {
         self.root_state = state.clone();
     }
 
-    fn do_iterations(&mut self, n: usize, rng: &mut SmallRng) {
+    fn do_iterations(&mut self, n: usize) {
         let mut al = ActionList::default();
         for _ in 0..n {
             self.root_node.iteration(
                 &mut al,
                 &mut self.root_state.clone(),
-                rng,
+                &mut self.rng,
                 &mut self.rave_table,
                 true,
             );
@@ -78,7 +86,6 @@ impl Mcts {
         println!("    Left Depth Iterations Value PV");
         let start_time = Instant::now();
         self.set_root(state);
-        let mut rng = SmallRng::from_entropy();
         let mut pv = ActionList::default();
         let mut iterations_per_ms = 5.;
         let mut iterations: usize = 0;
@@ -114,7 +121,7 @@ impl Mcts {
                         (1. - self.root_node.get_value()).min(1.0) * 100.,
                         pv
                     );
-                    let next_iterations = iterations_left as usize / 2;
+                    let next_iterations = iterations_left / 2;
                     (next_iterations, next_iterations < 100)
                 }
             } else {
@@ -123,7 +130,7 @@ impl Mcts {
             if stop {
                 break;
             }
-            self.do_iterations(next_iterations, &mut rng);
+            self.do_iterations(next_iterations);
             iterations += next_iterations;
             let elapsed = search_start_time.elapsed().as_micros() as f64;
             if elapsed > 0. {
@@ -152,6 +159,15 @@ impl Player for Mcts {
     fn on_reset(&mut self) {
         self.root_node = Node::empty();
         self.rave_table = RaveTable::default();
+        // Every game of a seeded search starts with the same random numbers
+        if let Some(seed) = self.seed {
+            self.rng = SmallRng::seed_from_u64(seed);
+        }
+    }
+
+    fn set_seed(&mut self, seed: u64) {
+        self.seed = Some(seed);
+        self.rng = SmallRng::seed_from_u64(seed);
     }
 
     fn set_time_limit(&mut self, time_limit: u128) {
@@ -167,6 +183,8 @@ impl Default for Mcts {
             root_state: GameState::default(),
             time_limit: Some(1960),
             iteration_limit: None,
+            rng: SmallRng::from_entropy(),
+            seed: None,
             rave_table: RaveTable::default(),
         }
     }
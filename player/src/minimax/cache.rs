@@ -38,7 +38,7 @@ pub struct TranspositionTableEntry {
 impl TranspositionTableEntry {
     #[inline(always)]
     pub fn is_empty(&self) -> bool {
-        self.depth_left == std::u8::MAX
+        self.depth_left == u8::MAX
     }
 
     pub fn empty() -> Self {
@@ -46,7 +46,7 @@ impl TranspositionTableEntry {
             action: Action::SKIP,
             score: 0,
             ply: 0,
-            depth_left: std::u8::MAX,
+            depth_left: u8::MAX,
             alpha: false,
             beta: false,
             hash: 0,
@@ -86,7 +86,7 @@ impl EvaluationCacheEntry {
     pub fn empty() -> Self {
         Self {
             hash: 0,
-            score: std::i16::MIN,
+            score: i16::MIN,
         }
     }
 }
@@ -12,12 +12,12 @@ pub struct EvaluationParameters {
 }
 
 const DEFAULT_PARAMS: EvaluationParameters = EvaluationParameters {
-    valuable_fields: Bitboard(
+    valuable_fields: Bitboard::from_u128s([
         4096,
         10966141185668210596808960154823295996,
         81778714517053366965726746058431544,
         1000815853617977686608561488208592896,
-    ),
+    ]),
     /*
     .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .
     .  1  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  .  1  .
@@ -48,9 +48,15 @@ const DEFAULT_PARAMS: EvaluationParameters = EvaluationParameters {
     double_placement_field_factor: -50.,
 };
 
+pub fn is_game_finished(state: &GameState) -> bool {
+    // The result can't change anymore once no color is able to place a piece.
+    // Only used in the evaluation of the leaves, where the cost is paid once per evaluated position.
+    state.is_game_over() || (0..4).all(|color| state.is_color_dead(color))
+}
+
 pub fn static_evaluation(state: &GameState) -> i16 {
     let team = state.get_team();
-    if state.is_game_over() {
+    if is_game_finished(state) {
         let result = state.game_result();
         return -if result > 0 {
             MATE_SCORE + result
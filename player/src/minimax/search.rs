@@ -4,7 +4,7 @@ use game_sdk::{Action, ActionList, ActionListStack, GameState, Player};
 use std::time::Instant;
 
 pub const MAX_SEARCH_DEPTH: usize = 40;
-pub const MAX_SCORE: i16 = std::i16::MAX;
+pub const MAX_SCORE: i16 = i16::MAX;
 pub const MATE_SCORE: i16 = 32_000;
 pub const TT_SIZE: usize = 20_000_000;
 pub const EVAL_CACHE_SIZE: usize = 1_000_000;
@@ -141,15 +141,14 @@ pub fn principal_variation_search(
     let is_pv_node = beta > 1 + alpha;
     let original_alpha = alpha;
 
+    #[allow(clippy::manual_is_multiple_of)] // is_multiple_of needs Rust 1.87
     if searcher.nodes_searched % 4096 == 0 {
         searcher.stop = searcher.start_time.elapsed().as_millis() >= searcher.time_limit;
     }
 
     if depth_left == 0 || searcher.stop || state.is_game_over() {
         let evaluation_cache_entry = searcher.evaluation_cache.lookup(state.hash);
-        if evaluation_cache_entry.hash == state.hash
-            && evaluation_cache_entry.score != std::i16::MIN
-        {
+        if evaluation_cache_entry.hash == state.hash && evaluation_cache_entry.score != i16::MIN {
             return evaluation_cache_entry.score;
         } else {
             let score = static_evaluation(state);
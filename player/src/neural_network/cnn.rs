@@ -1,7 +1,7 @@
 use super::convolutional_layer::ConvolutionalLayer;
 use super::dense_layer::DenseLayer;
 use super::float_stuff::{relu, sigmoid};
-use game_sdk::{Action, ActionList, Bitboard, GameState, Player};
+use game_sdk::{Action, ActionList, Bitboard, GameState, Player, Symmetry};
 use std::fmt::{Display, Formatter, Result};
 use std::fs::File;
 use std::io::Read;
@@ -20,30 +20,20 @@ pub fn state_to_vector(state: &GameState, al: &mut ActionList) -> Vec<Vec<Vec<f3
         let piece = Bitboard::with_piece(destination, shape);
         reachable_fields |= piece;
     }
-    while reachable_fields.not_empty() {
-        let field_index = reachable_fields.trailing_zeros();
-        reachable_fields.flip_bit(field_index);
-        let x = field_index % 21;
-        let y = (field_index - x) / 21;
-        vector[x as usize][y as usize][4] = 1.;
-    }
-    let mut current_ply = state.ply as usize;
-    for i in 0..4 {
-        let mut board = state.board[i];
-        while board.not_empty() {
-            let field_index = board.trailing_zeros();
-            board.flip_bit(field_index);
-            let x = field_index % 21;
-            let y = (field_index - x) / 21;
-            vector[x as usize][y as usize][current_ply & 0b11] = 1.;
+    for field in reachable_fields.fields() {
+        vector[field.x() as usize][field.y() as usize][4] = 1.;
+    }
+    for (i, board) in state.board.iter().enumerate() {
+        let channel = (state.ply as usize + i) & 0b11;
+        for field in board.fields() {
+            vector[field.x() as usize][field.y() as usize][channel] = 1.;
         }
-        current_ply += 1;
     }
     vector
 }
 
 pub fn flatten(vector: Vec<Vec<Vec<f32>>>) -> Vec<f32> {
-    vector.into_iter().flatten().into_iter().flatten().collect()
+    vector.into_iter().flatten().flatten().collect()
 }
 
 pub struct NeuralNetwork {
@@ -156,7 +146,7 @@ impl Player for NeuralNetwork {
         let mut al = ActionList::default();
         let input = state_to_vector(&state, &mut al);
         let output = self.feed_forward(input);
-        let mut best_value = std::f32::NEG_INFINITY;
+        let mut best_value = f32::NEG_INFINITY;
         let mut best_action = al[0];
         for i in 0..al.size {
             let action = al[i];
@@ -166,14 +156,9 @@ impl Player for NeuralNetwork {
             let mut value: f32 = 0.;
             let destination = action.get_destination();
             let shape = action.get_shape() as usize;
-            let mut piece = Bitboard::with_piece(destination, shape);
-            piece = r.rotate_bitboard(piece);
-            while piece.not_empty() {
-                let field_index = piece.trailing_zeros();
-                piece.flip_bit(field_index);
-                let x = field_index % 21;
-                let y = (field_index - x) / 21;
-                value += output[(x + y * 20) as usize];
+            let piece = r.rotate_bitboard(Bitboard::with_piece(destination, shape));
+            for field in piece.fields() {
+                value += output[(field.x() + field.y() * 20) as usize];
             }
             if value > best_value {
                 best_value = value;
@@ -185,55 +170,38 @@ impl Player for NeuralNetwork {
 }
 
 pub struct BoardRotation {
-    top_left_corner: u8,
+    symmetry: Symmetry,
 }
 
 impl BoardRotation {
     pub fn rotate_state(state: &mut GameState) -> BoardRotation {
+        // Moves the start corner of the current color to the top left corner
         let board = state.board[state.get_current_color()];
-        let top_left_corner = if board.check_bit(0) {
-            0
+        let symmetry = if board.check_bit(0) {
+            Symmetry::Identity
         } else if board.check_bit(19) {
-            1
+            Symmetry::Mirror
         } else if board.check_bit(399) {
-            2
+            Symmetry::Flip
         } else {
-            3
+            Symmetry::Rotate180
         };
-        let board_rotation = Self { top_left_corner };
+        // Only the boards are used for the input of the network, so the hash is not updated
         for board in state.board.iter_mut() {
-            *board = board_rotation.rotate_bitboard(*board);
+            *board = symmetry.transform_bitboard(*board);
         }
-        board_rotation
+        Self { symmetry }
     }
 
     pub fn rotate_bitboard(&self, board: Bitboard) -> Bitboard {
-        match self.top_left_corner {
-            1 => board.mirror(),
-            2 => board.flip(),
-            3 => board.rotate_left().rotate_left(),
-            _ => board,
-        }
+        self.symmetry.transform_bitboard(board)
     }
 
     pub fn rotate_bitboard_back(&self, board: Bitboard) -> Bitboard {
-        match self.top_left_corner {
-            1 => board.mirror(),
-            2 => board.flip(),
-            3 => board.rotate_left().rotate_left(),
-            _ => board,
-        }
+        self.symmetry.inverse().transform_bitboard(board)
     }
 
     pub fn rotate_action(&self, action: Action) -> Action {
-        if action.is_set() {
-            let destination = action.get_destination();
-            let shape = action.get_shape() as usize;
-            Action::from_bitboard(
-                self.rotate_bitboard_back(Bitboard::with_piece(destination, shape)),
-            )
-        } else {
-            action
-        }
+        self.symmetry.inverse().transform_action(action)
     }
 }
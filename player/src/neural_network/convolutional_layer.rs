@@ -23,6 +23,7 @@ impl ConvolutionalLayer {
         }
     }
 
+    #[allow(clippy::needless_range_loop)]
     pub fn feed_forward(&self, input: Vec<Vec<Vec<f32>>>) -> Vec<Vec<Vec<f32>>> {
         let input_shape = (input.len(), input[0].len());
         let mut output = vec![vec![vec![0.; self.channels]; input_shape.0]; input_shape.1];
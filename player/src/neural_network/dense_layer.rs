@@ -70,7 +70,7 @@ impl Display for DenseLayer {
             f,
             "Dense      | {:19} | {}",
             shape,
-            if self.activation as usize == sigmoid as usize {
+            if self.activation as usize == sigmoid as *const () as usize {
                 "Sigmoid"
             } else {
                 "ReLU"
@@ -1,5 +1,9 @@
-use argparse::{ArgumentParser, Store};
-use game_sdk::{Action, ActionList, GameState, Player};
+use argparse::{ArgumentParser, Store, StoreOption};
+use game_sdk::{
+    Action, ActionList, GameRecord, GameState, Player, Rules, StartPiecePolicy, TerminationCause,
+};
+use rand::{rngs::SmallRng, RngCore, SeedableRng};
+use std::fs::File;
 use std::io::{BufRead, BufReader, Write};
 use std::process::{ChildStdin, ChildStdout, Command, Stdio};
 use std::time::Instant;
@@ -13,9 +17,12 @@ pub struct Client {
 
 impl Client {
     pub fn from_path(path: String, time: u64) -> Self {
-        let mut process = Command::new(path.clone())
-            .args(&["--time", &time.to_string()])
-            .args(&["--test", "true"])
+        let mut command = Command::new(path.clone());
+        command
+            .args(["--time", &time.to_string()])
+            .args(["--test", "true"]);
+        #[allow(clippy::zombie_processes)]
+        let mut process = command
             .stdin(Stdio::piped())
             .stdout(Stdio::piped())
             .stderr(Stdio::piped())
@@ -28,6 +35,14 @@ impl Client {
             time,
         }
     }
+
+    pub fn send_seed(&mut self, seed: u64) {
+        // The player of the client is seeded again before each game, so that a game only depends
+        // on its own seed and not on the games before it
+        self.stdin
+            .write_all(format!("seed: {}\n", seed).as_bytes())
+            .unwrap();
+    }
 }
 
 impl Player for Client {
@@ -41,7 +56,7 @@ impl Player for Client {
         loop {
             read.read_line(&mut line).unwrap();
             if !line.is_empty() && line.contains("action: ") {
-                line = (&line[8..]).to_string();
+                line = line[8..].to_string();
                 break;
             }
             if !line.is_empty() {
@@ -65,40 +80,68 @@ impl Player for Client {
     fn on_reset(&mut self) {}
 }
 
-pub fn play_game(client_one: &mut Client, client_two: &mut Client, first: u8) {
-    let mut state = GameState::random();
+pub fn play_game(
+    client_one: &mut Client,
+    client_two: &mut Client,
+    first: u8,
+    seed: u64,
+    rules: Rules,
+) -> GameRecord {
+    // The start piece type only depends on the seed. The clients receive the rules with the fen
+    // and are seeded by main if a seed was given.
+    let mut state = GameState::with_rules_and_seed(rules, seed);
     let mut al = ActionList::default();
+    let mut player_names = [client_one.path.clone(), client_two.path.clone()];
+    if first == 1 {
+        player_names.swap(0, 1);
+    }
+    let mut record = GameRecord::new(state.start_piece_type, player_names);
+    record.rules = rules;
+    let mut cause = TerminationCause::Regular;
     while !state.is_game_over() {
-        state.get_possible_actions(&mut al);
-        if al[0].is_skip() {
-            state.do_action(al[0]);
+        // Colors that can't place any more pieces skip without asking the client.
+        // is_color_dead is only a fast path, the possible actions decide.
+        let mut must_skip = state.is_color_dead(state.get_current_color());
+        if !must_skip {
+            state.get_possible_actions(&mut al);
+            must_skip = al[0].is_skip();
+        }
+        if must_skip {
+            record.push(Action::SKIP, 0);
+            state.do_action(Action::SKIP);
             continue;
         }
-        let action = if state.ply % 2 == first {
-            client_one.on_move_request(&state)
+        let start_time = Instant::now();
+        let client = if state.ply % 2 == first {
+            &mut *client_one
         } else {
-            client_two.on_move_request(&state)
+            &mut *client_two
         };
-        state.do_action(action);
-    }
-    let result = state.game_result() as i64;
-    let mut scores: [u32; 4] = [0, 0, 0, 0];
-    for (color, score) in scores.iter_mut().enumerate() {
-        *score = state.board[color].count_ones();
-        if *score == 89 {
-            *score += 15;
-            if state.monomino_placed_last[color] {
-                *score += 5;
-            }
+        let action = client.on_move_request(&state);
+        if let Err(reason) = state.check_action(&action) {
+            println!(
+                "warning: Client {} sent an illegal action ({}): {}",
+                client.path, action, reason
+            );
+            cause = TerminationCause::RuleViolation;
+            break;
         }
+        record.push(action, start_time.elapsed().as_millis() as u64);
+        state.do_action(action);
     }
+    record.finish(&state, cause);
+    let report = state.scores();
+    let result = if cause == TerminationCause::RuleViolation {
+        // The team that sent the illegal action loses
+        state.get_team() as i64
+    } else {
+        report.difference() as i64
+    };
     println!(
         "result: {} {} {} {}",
-        first,
-        result,
-        scores[0] + scores[2],
-        scores[1] + scores[3],
+        first, result, report.teams[0], report.teams[1],
     );
+    record
 }
 
 fn main() {
@@ -106,6 +149,9 @@ fn main() {
     let mut client_two_path = String::new();
     let mut games: u64 = 1000;
     let mut time: u64 = 1600;
+    let mut records = String::new();
+    let mut seed: Option<u64> = None;
+    let mut start_piece = "random".to_string();
 
     {
         let mut parser = ArgumentParser::new();
@@ -121,6 +167,24 @@ fn main() {
         parser
             .refer(&mut time)
             .add_option(&["-t", "--time"], Store, "Time/Action in milliseconds");
+        parser.refer(&mut records).add_option(
+            &["-r", "--records"],
+            Store,
+            "Directory in which the game records are saved",
+        );
+        parser.refer(&mut seed).add_option(
+            &["-s", "--seed"],
+            StoreOption,
+            "Seed of the first game (each game also seeds the clients, \
+             which requires a client that reads the seed line). \
+             Games with time-limited clients (MCTS, minimax) can still differ, \
+             because the number of searched nodes depends on the time",
+        );
+        parser.refer(&mut start_piece).add_option(
+            &["--start-piece"],
+            Store,
+            "Start piece policy: random, free or the short name of a fixed start piece",
+        );
         parser.parse_args_or_exit();
     }
 
@@ -128,12 +192,41 @@ fn main() {
     println!("info: client_two_path: {}", client_two_path);
     println!("info: games: {}", games);
     println!("info: time: {}", time);
+    println!("info: records: {}", records);
+    let rules = Rules {
+        start_piece_policy: StartPiecePolicy::from_name(&start_piece)
+            .unwrap_or_else(|| panic!("Invalid start piece policy: {}", start_piece)),
+    };
+    println!("info: start piece: {}", rules.start_piece_policy);
+    // Game n uses the seed seed + n. Without a given seed, the seed is chosen randomly and printed for each game.
+    // The clients are only seeded with a given seed, because older clients can't read the seed line.
+    let seed_clients = seed.is_some();
+    let seed = seed.unwrap_or_else(|| SmallRng::from_entropy().next_u64());
 
     let mut client_one = Client::from_path(client_one_path, time);
     let mut client_two = Client::from_path(client_two_path, time);
     std::thread::sleep(std::time::Duration::from_millis(1000));
+    let mut game: usize = 0;
     loop {
-        play_game(&mut client_one, &mut client_two, 0);
-        play_game(&mut client_one, &mut client_two, 1);
+        for first in 0..2 {
+            let game_seed = seed.wrapping_add(game as u64);
+            println!("info: game {} seed: {}", game, game_seed);
+            if seed_clients {
+                client_one.send_seed(game_seed);
+                client_two.send_seed(game_seed);
+            }
+            let record = play_game(&mut client_one, &mut client_two, first, game_seed, rules);
+            if !records.is_empty() {
+                let path = format!("{}/game_{}.txt", records, game);
+                match record.serialize() {
+                    Ok(serialized) => match File::create(&path) {
+                        Ok(mut file) => file.write_all(serialized.as_bytes()).unwrap(),
+                        Err(error) => println!("warning: Unable to save {}: {}", path, error),
+                    },
+                    Err(error) => println!("warning: Unable to save {}: {}", path, error),
+                }
+            }
+            game += 1;
+        }
     }
 }